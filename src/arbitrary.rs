@@ -0,0 +1,125 @@
+//! Input strings for fuzzing with [`arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/).
+//!
+//! Valid strings cover every unit spelling, optional `+` sign and whitespace
+//! around the number and the unit; they are always accepted by `FromStr` implementation.
+//! Nearly valid strings are valid strings with a few characters removed or inserted;
+//! they may or may not be accepted.
+//! All size and duration types implement [`Arbitrary`](::arbitrary::Arbitrary) with this feature enabled;
+//! [`BoundedSize`](crate::BoundedSize) and [`BoundedDuration`](crate::BoundedDuration)
+//! generate only the values within their bounds.
+//!
+//! ```rust
+//! use arbitrary::Unstructured;
+//! use human_units::Size;
+//!
+//! let mut u = Unstructured::new(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+//! let s = human_units::arbitrary::size_str(&mut u).unwrap();
+//! assert!(s.parse::<Size>().is_ok());
+//! let s = human_units::arbitrary::nearly_valid_size_str(&mut u).unwrap();
+//! let _ = s.parse::<Size>();
+//! ```
+
+use alloc::string::String;
+use alloc::string::ToString;
+
+use ::arbitrary::Result;
+use ::arbitrary::Unstructured;
+
+use crate::mutate;
+use crate::ValidStr;
+use crate::DURATION_SPELLINGS;
+use crate::DURATION_TOKENS;
+use crate::SIZE_SPELLINGS;
+use crate::SIZE_TOKENS;
+use crate::WHITESPACE;
+
+/// Generates a string that is accepted by [`Size::from_str`](crate::Size).
+pub fn size_str(u: &mut Unstructured) -> Result<String> {
+    valid_str(u, &SIZE_SPELLINGS)
+}
+
+/// Generates a string that is similar to the ones accepted by [`Size::from_str`](crate::Size).
+pub fn nearly_valid_size_str(u: &mut Unstructured) -> Result<String> {
+    nearly_valid_str(u, &SIZE_SPELLINGS, &SIZE_TOKENS)
+}
+
+/// Generates a string that is accepted by [`Duration::from_str`](crate::Duration).
+pub fn duration_str(u: &mut Unstructured) -> Result<String> {
+    valid_str(u, &DURATION_SPELLINGS)
+}
+
+/// Generates a string that is similar to the ones accepted by [`Duration::from_str`](crate::Duration).
+pub fn nearly_valid_duration_str(u: &mut Unstructured) -> Result<String> {
+    nearly_valid_str(u, &DURATION_SPELLINGS, &DURATION_TOKENS)
+}
+
+fn valid_str(u: &mut Unstructured, spellings: &[(&'static str, u128)]) -> Result<String> {
+    let (unit, max) = *u.choose(spellings)?;
+    Ok(ValidStr {
+        sign: u.arbitrary()?,
+        prefix: u.choose(&WHITESPACE)?,
+        number: u.int_in_range(0..=max)?,
+        infix: u.choose(&WHITESPACE)?,
+        unit,
+        suffix: u.choose(&WHITESPACE)?,
+    }
+    .to_string())
+}
+
+fn nearly_valid_str(
+    u: &mut Unstructured,
+    spellings: &[(&'static str, u128)],
+    tokens: &[&str],
+) -> Result<String> {
+    let mut s = valid_str(u, spellings)?;
+    for _ in 0..u.int_in_range(1..=3)? {
+        let token = if u.arbitrary()? {
+            Some(*u.choose(tokens)?)
+        } else {
+            None
+        };
+        mutate(&mut s, u.arbitrary()?, token);
+    }
+    Ok(s)
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+    use crate::Duration;
+    use crate::Size;
+
+    #[test]
+    fn test_valid_str() {
+        arbtest(|u| {
+            let s = size_str(u)?;
+            assert!(s.parse::<Size>().is_ok(), "s = `{}`", s);
+            let s = duration_str(u)?;
+            assert!(s.parse::<Duration>().is_ok(), "s = `{}`", s);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_nearly_valid_str() {
+        let mut sizes = [0_usize; 2];
+        let mut durations = [0_usize; 2];
+        arbtest(|u| {
+            let s = nearly_valid_size_str(u)?;
+            sizes[s.parse::<Size>().is_ok() as usize] += 1;
+            let s = nearly_valid_duration_str(u)?;
+            durations[s.parse::<Duration>().is_ok() as usize] += 1;
+            Ok(())
+        })
+        .budget_ms(100);
+        assert!(sizes.iter().all(|n| *n != 0), "sizes = {:?}", sizes);
+        assert!(
+            durations.iter().all(|n| *n != 0),
+            "durations = {:?}",
+            durations
+        );
+    }
+}
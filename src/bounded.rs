@@ -0,0 +1,598 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::ErrorKind;
+use crate::Size;
+
+/**
+Exact size that is within `MIN..=MAX` bytes.
+
+The bounds are checked by [`new`](BoundedSize::new), `FromStr` and serde deserialization;
+the error message contains the bounds in human-readable form
+(`size must be from 1k to 1g`).
+
+`MIN` must not be greater than `MAX`; otherwise the constructors fail to compile.
+
+```compile_fail
+use human_units::{BoundedSize, Size};
+let _ = BoundedSize::<2, 1>::new(Size(1));
+```
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct BoundedSize<const MIN: u64, const MAX: u64>(Size);
+
+impl<const MIN: u64, const MAX: u64> BoundedSize<MIN, MAX> {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = Size::MAX_STRING_LEN;
+
+    const _CHECK: () = assert!(MIN <= MAX, "`MIN` is greater than `MAX`");
+
+    /// Returns the size if it is within the bounds.
+    pub const fn new(size: Size) -> Result<Self, BoundedSizeError> {
+        let () = Self::_CHECK;
+        if MIN <= size.0 && size.0 <= MAX {
+            Ok(Self(size))
+        } else {
+            Err(BoundedSizeError::OutOfRange {
+                min: Size(MIN),
+                max: Size(MAX),
+            })
+        }
+    }
+
+    /// Returns the size.
+    pub const fn get(self) -> Size {
+        self.0
+    }
+}
+
+impl<const MIN: u64, const MAX: u64> Display for BoundedSize<MIN, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const MIN: u64, const MAX: u64> FromStr for BoundedSize<MIN, MAX> {
+    type Err = BoundedSizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        Self::new(Size::parse(other)?)
+    }
+}
+
+impl<const MIN: u64, const MAX: u64> TryFrom<Size> for BoundedSize<MIN, MAX> {
+    type Error = BoundedSizeError;
+    fn try_from(other: Size) -> Result<Self, Self::Error> {
+        Self::new(other)
+    }
+}
+
+impl<const MIN: u64, const MAX: u64> From<BoundedSize<MIN, MAX>> for Size {
+    fn from(other: BoundedSize<MIN, MAX>) -> Self {
+        other.0
+    }
+}
+
+impl<const MIN: u64, const MAX: u64> Deref for BoundedSize<MIN, MAX> {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Bounded size parsing error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedSizeError {
+    /// The string is not a valid size.
+    Invalid(ErrorKind),
+    /// The size is not within the bounds.
+    OutOfRange {
+        /// Min. size.
+        min: Size,
+        /// Max. size.
+        max: Size,
+    },
+}
+
+impl Display for BoundedSizeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Invalid(reason) => write!(f, "invalid size ({})", reason),
+            Self::OutOfRange { min, max } => write!(f, "size must be from {} to {}", min, max),
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl std::error::Error for BoundedSizeError {}
+
+impl From<ErrorKind> for BoundedSizeError {
+    fn from(other: ErrorKind) -> Self {
+        Self::Invalid(other)
+    }
+}
+
+/**
+Exact duration that is within `MIN_NS..=MAX_NS` nanoseconds.
+
+The bounds are checked by [`new`](BoundedDuration::new), `FromStr` and serde deserialization;
+the error message contains the bounds in human-readable form
+(`duration must be from 1s to 1h`).
+
+`MIN_NS` must not be greater than `MAX_NS`; otherwise the constructors fail to compile.
+
+```compile_fail
+use human_units::{BoundedDuration, Duration};
+let _ = BoundedDuration::<2, 1>::new(Duration::default());
+```
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct BoundedDuration<const MIN_NS: u64, const MAX_NS: u64>(Duration);
+
+impl<const MIN_NS: u64, const MAX_NS: u64> BoundedDuration<MIN_NS, MAX_NS> {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = Duration::MAX_STRING_LEN;
+
+    const _CHECK: () = assert!(MIN_NS <= MAX_NS, "`MIN_NS` is greater than `MAX_NS`");
+
+    /// Returns the duration if it is within the bounds.
+    pub const fn new(duration: Duration) -> Result<Self, BoundedDurationError> {
+        let () = Self::_CHECK;
+        let nanos = duration.0.as_nanos();
+        if MIN_NS as u128 <= nanos && nanos <= MAX_NS as u128 {
+            Ok(Self(duration))
+        } else {
+            Err(BoundedDurationError::OutOfRange {
+                min: Duration(StdDuration::from_nanos(MIN_NS)),
+                max: Duration(StdDuration::from_nanos(MAX_NS)),
+            })
+        }
+    }
+
+    /// Returns the duration.
+    pub const fn get(self) -> Duration {
+        self.0
+    }
+}
+
+impl<const MIN_NS: u64, const MAX_NS: u64> Display for BoundedDuration<MIN_NS, MAX_NS> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const MIN_NS: u64, const MAX_NS: u64> FromStr for BoundedDuration<MIN_NS, MAX_NS> {
+    type Err = BoundedDurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        Self::new(Duration::parse(other)?)
+    }
+}
+
+impl<const MIN_NS: u64, const MAX_NS: u64> TryFrom<Duration> for BoundedDuration<MIN_NS, MAX_NS> {
+    type Error = BoundedDurationError;
+    fn try_from(other: Duration) -> Result<Self, Self::Error> {
+        Self::new(other)
+    }
+}
+
+impl<const MIN_NS: u64, const MAX_NS: u64> From<BoundedDuration<MIN_NS, MAX_NS>> for Duration {
+    fn from(other: BoundedDuration<MIN_NS, MAX_NS>) -> Self {
+        other.0
+    }
+}
+
+impl<const MIN_NS: u64, const MAX_NS: u64> Deref for BoundedDuration<MIN_NS, MAX_NS> {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Bounded duration parsing error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedDurationError {
+    /// The string is not a valid duration.
+    Invalid(ErrorKind),
+    /// The duration is not within the bounds.
+    OutOfRange {
+        /// Min. duration.
+        min: Duration,
+        /// Max. duration.
+        max: Duration,
+    },
+}
+
+impl Display for BoundedDurationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Invalid(reason) => write!(f, "invalid duration ({})", reason),
+            Self::OutOfRange { min, max } => {
+                write!(f, "duration must be from {} to {}", min, max)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl std::error::Error for BoundedDurationError {}
+
+impl From<ErrorKind> for BoundedDurationError {
+    fn from(other: ErrorKind) -> Self {
+        Self::Invalid(other)
+    }
+}
+
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a, const MIN: u64, const MAX: u64> arbitrary::Arbitrary<'a> for BoundedSize<MIN, MAX> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let () = Self::_CHECK;
+        Ok(Self(Size(u.int_in_range(MIN..=MAX)?)))
+    }
+}
+
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a, const MIN_NS: u64, const MAX_NS: u64> arbitrary::Arbitrary<'a>
+    for BoundedDuration<MIN_NS, MAX_NS>
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let () = Self::_CHECK;
+        let nanos = u.int_in_range(MIN_NS..=MAX_NS)?;
+        Ok(Self(Duration(StdDuration::from_nanos(nanos))))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN: u64, const MAX: u64> serde::Serialize for BoundedSize<MIN, MAX> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.0, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, const MIN: u64, const MAX: u64> serde::Deserialize<'a> for BoundedSize<MIN, MAX> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::size_serde::deserialize_size_checked(d, Self::new)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const MIN_NS: u64, const MAX_NS: u64> serde::Serialize for BoundedDuration<MIN_NS, MAX_NS> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.0, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, const MIN_NS: u64, const MAX_NS: u64> serde::Deserialize<'a>
+    for BoundedDuration<MIN_NS, MAX_NS>
+{
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::duration_serde::deserialize_duration_checked(d, Self::new)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<const MIN: u64, const MAX: u64> schemars::JsonSchema for BoundedSize<MIN, MAX> {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("BoundedSize_from_{}_to_{}", Size(MIN), Size(MAX)).into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("human_units::BoundedSize<{}, {}>", MIN, MAX).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let (min, max) = (Size(MIN), Size(MAX));
+        bounded_schema(
+            <Size as schemars::JsonSchema>::json_schema(generator),
+            alloc::format!(
+                "Size from {} to {} with optional unit k, m, g or t (powers of 1024) or the number of bytes; \
+                the bounds of the string are checked only when the value is parsed",
+                min, max
+            ),
+            MIN,
+            MAX,
+            [alloc::format!("{}", min), alloc::format!("{}", max)],
+        )
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<const MIN_NS: u64, const MAX_NS: u64> schemars::JsonSchema
+    for BoundedDuration<MIN_NS, MAX_NS>
+{
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        let min = Duration(StdDuration::from_nanos(MIN_NS));
+        let max = Duration(StdDuration::from_nanos(MAX_NS));
+        alloc::format!("BoundedDuration_from_{}_to_{}", min, max).into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("human_units::BoundedDuration<{}, {}>", MIN_NS, MAX_NS).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let min = Duration(StdDuration::from_nanos(MIN_NS));
+        let max = Duration(StdDuration::from_nanos(MAX_NS));
+        // the number of seconds is an integer
+        bounded_schema(
+            <Duration as schemars::JsonSchema>::json_schema(generator),
+            alloc::format!(
+                "Duration from {} to {} with optional unit ns, μs, ms, s, m, h or d or the number of seconds; \
+                the bounds of the string are checked only when the value is parsed",
+                min, max
+            ),
+            MIN_NS / NANOS_PER_SEC + (MIN_NS % NANOS_PER_SEC != 0) as u64,
+            MAX_NS / NANOS_PER_SEC,
+            [alloc::format!("{}", min), alloc::format!("{}", max)],
+        )
+    }
+}
+
+/// Creates the schema that checks the bounds of integers and the pattern of strings.
+#[cfg(feature = "schemars")]
+fn bounded_schema(
+    schema: schemars::Schema,
+    description: alloc::string::String,
+    min: u64,
+    max: u64,
+    examples: [alloc::string::String; 2],
+) -> schemars::Schema {
+    schemars::json_schema!({
+        "description": description,
+        "anyOf": [
+            {
+                "type": "integer",
+                "minimum": min,
+                "maximum": max,
+            },
+            {
+                "type": "string",
+                "pattern": schema.get("pattern"),
+            },
+        ],
+        "examples": examples,
+    })
+}
+
+#[cfg(feature = "schemars")]
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    type Buffer = BoundedSize<1024, { 1024 * 1024 * 1024 }>;
+    type Timeout = BoundedDuration<1_000_000_000, { 3600 * 1_000_000_000 }>;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Size(1024), "1k".parse::<Buffer>().unwrap().get());
+        assert_eq!(Size(1 << 30), "1g".parse::<Buffer>().unwrap().get());
+        assert_eq!(
+            Duration(StdDuration::from_secs(1)),
+            "1s".parse::<Timeout>().unwrap().get()
+        );
+        assert_eq!(
+            Duration(StdDuration::from_secs(3600)),
+            "1h".parse::<Timeout>().unwrap().get()
+        );
+        assert_eq!(
+            Err(BoundedSizeError::OutOfRange {
+                min: Size(1024),
+                max: Size(1 << 30)
+            }),
+            "1023".parse::<Buffer>()
+        );
+        assert_eq!(
+            Err(BoundedSizeError::Invalid(ErrorKind::UnknownUnit)),
+            "1x".parse::<Buffer>()
+        );
+        assert_eq!(
+            Err(BoundedDurationError::Invalid(ErrorKind::NoNumber)),
+            "s".parse::<Timeout>()
+        );
+        assert_eq!(
+            "invalid size (the value is too large)",
+            "99999999999999999999"
+                .parse::<Buffer>()
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "size must be from 1k to 1g",
+            "1025m".parse::<Buffer>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "duration must be from 1s to 1h",
+            "999ms".parse::<Timeout>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "duration must be from 1s to 1h",
+            "3601s".parse::<Timeout>().unwrap_err().to_string()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_io() {
+        assert_eq!(
+            "\"1k\"",
+            serde_json::to_string(&"1k".parse::<Buffer>().unwrap()).unwrap()
+        );
+        assert_eq!(
+            Size(1 << 20),
+            serde_json::from_str::<Buffer>("\"1m\"").unwrap().get()
+        );
+        assert_eq!(
+            Size(4096),
+            serde_json::from_str::<Buffer>("4096").unwrap().get()
+        );
+        assert_eq!(
+            "size must be from 1k to 1g at line 1 column 3",
+            serde_json::from_str::<Buffer>("\"1\"")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "size must be from 1k to 1g at line 1 column 3",
+            serde_json::from_str::<Buffer>("100")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "duration must be from 1s to 1h at line 1 column 23",
+            serde_json::from_str::<Timeout>(r#"{"secs":0,"nanos":5000}"#)
+                .unwrap_err()
+                .to_string()
+        );
+        for expected in [
+            Buffer::new(Size(1024)).unwrap(),
+            Buffer::new(Size(1 << 30)).unwrap(),
+        ] {
+            let bytes = bincode::serialize(&expected).unwrap();
+            assert_eq!(expected, bincode::deserialize(&bytes).unwrap());
+        }
+        assert!(bincode::deserialize::<Buffer>(&bincode::serialize(&Size(1)).unwrap()).is_err());
+        assert_eq!(
+            "duration must be from 1s to 1h",
+            serde_yaml::from_str::<Timeout>("2h")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "schemars"))]
+    #[test]
+    fn test_schema() {
+        use crate::str_schema::tests::check_schema;
+
+        // the bounds are only checked for integers
+        check_schema::<Buffer>(
+            &["0", "1", "9", "10", "k", "g", "t", "x", " ", "+", "."],
+            7,
+            |_| true,
+        );
+        check_schema::<Timeout>(
+            &["0", "1", "9", "10", "ms", "s", "h", "x", " ", "+", "."],
+            7,
+            |_| true,
+        );
+        let schema = serde_json::to_value(schemars::schema_for!(Buffer)).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for s in ["0", "1023", "1024", "1073741824", "1073741825", "-1"] {
+            assert_eq!(
+                serde_json::from_str::<Buffer>(s).is_ok(),
+                validator.is_valid(&serde_json::from_str(s).unwrap()),
+                "s = `{}`",
+                s
+            );
+        }
+        // strings that are out of range are valid according to the schema
+        for (s, valid, parsed) in [
+            ("\"1k\"", true, true),
+            ("\"1g\"", true, true),
+            ("\"2g\"", true, false),
+            ("\"1023\"", true, false),
+            ("\"1x\"", false, false),
+        ] {
+            let value: serde_json::Value = serde_json::from_str(s).unwrap();
+            assert_eq!(valid, validator.is_valid(&value), "s = `{}`", s);
+            assert_eq!(
+                parsed,
+                serde_json::from_str::<Buffer>(s).is_ok(),
+                "s = `{}`",
+                s
+            );
+        }
+        let schema = serde_json::to_value(schemars::schema_for!(Timeout)).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for s in ["0", "1", "3600", "3601"] {
+            assert_eq!(
+                serde_json::from_str::<Timeout>(s).is_ok(),
+                validator.is_valid(&serde_json::from_str(s).unwrap()),
+                "s = `{}`",
+                s
+            );
+        }
+        assert_eq!(
+            "Size from 1k to 1g with optional unit k, m, g or t (powers of 1024) or the number of bytes; \
+            the bounds of the string are checked only when the value is parsed",
+            schemars::schema_for!(Buffer).get("description").unwrap()
+        );
+        assert_eq!(
+            "Duration from 1s to 1h with optional unit ns, μs, ms, s, m, h or d or the number of seconds; \
+            the bounds of the string are checked only when the value is parsed",
+            schemars::schema_for!(Timeout).get("description").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_clap() {
+        use clap::Parser;
+
+        #[derive(Parser, Debug)]
+        struct Args {
+            #[arg(long, value_parser = clap::value_parser!(Buffer))]
+            buffer: Buffer,
+        }
+
+        let args = Args::parse_from(["test", "--buffer", "4k"]);
+        assert_eq!(Size(4096), args.buffer.get());
+        let error = Args::try_parse_from(["test", "--buffer", "2g"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("size must be from 1k to 1g"),
+            "error = `{}`",
+            error
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: Buffer = u.arbitrary()?;
+            let actual: Buffer = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let expected: Timeout = u.arbitrary()?;
+            let actual: Timeout = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_arbitrary() {
+        arbtest(|u| {
+            let value: Buffer = u.arbitrary()?;
+            assert_eq!(Ok(value), Buffer::new(value.get()));
+            let value: Timeout = u.arbitrary()?;
+            assert_eq!(Ok(value), Timeout::new(value.get()));
+            let value: BoundedSize<1, 1> = u.arbitrary()?;
+            assert_eq!(Size(1), value.get());
+            Ok(())
+        });
+    }
+}
@@ -46,6 +46,13 @@ impl<const N: usize> Buffer<N> {
     }
 }
 
+#[cfg(feature = "http")]
+impl<const N: usize> AsRef<[u8]> for Buffer<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 impl<const N: usize> core::fmt::Write for Buffer<N> {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
         let bytes = s.as_bytes();
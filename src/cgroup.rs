@@ -0,0 +1,611 @@
+use core::fmt::Display;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::parse_decimal;
+use crate::Duration;
+use crate::DurationError;
+use crate::Limit;
+use crate::Size;
+use crate::SizeError;
+
+/**
+Memory limit in cgroup v2 interface file format (`memory.max`, `memory.high`, `memory.swap.max`).
+
+[`Limit::Unlimited`] means no limit and is written as `max`.
+
+Parsing accepts `max` and any string accepted by [`Size`]:
+the number of bytes as written by the kernel (`536870912`)
+as well as human-readable sizes (`512m`).
+Formatting produces `max` or the number of bytes.
+Note that the kernel rounds the limit down to the page size.
+
+No limit is greater than any finite limit.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct CgroupMemoryLimit(pub Limit<Size>);
+
+impl CgroupMemoryLimit {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+
+    /// No limit.
+    pub const MAX: Self = Self(Limit::Unlimited);
+}
+
+impl Display for CgroupMemoryLimit {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.0 {
+            Limit::Finite(size) => write!(f, "{}", size.0),
+            Limit::Unlimited => f.write_str(MAX),
+        }
+    }
+}
+
+impl FromStr for CgroupMemoryLimit {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        parse_max(other, str::parse).map(Self)
+    }
+}
+
+impl From<Size> for CgroupMemoryLimit {
+    fn from(other: Size) -> Self {
+        Self(Limit::Finite(other))
+    }
+}
+
+impl From<Limit<Size>> for CgroupMemoryLimit {
+    fn from(other: Limit<Size>) -> Self {
+        Self(other)
+    }
+}
+
+impl From<CgroupMemoryLimit> for Limit<Size> {
+    fn from(other: CgroupMemoryLimit) -> Self {
+        other.0
+    }
+}
+
+/**
+CPU bandwidth limit in cgroup v2 `cpu.max` file format (`50000 100000`).
+
+The group may use up to `quota` of CPU time in each `period`.
+[`Unlimited`](Limit::Unlimited) quota means no limit and is written as `max`.
+
+Parsing accepts the quota (or `max`) and the period in microseconds separated by whitespace,
+as well as human-readable durations separated by a slash (`50ms/100ms`, `max/100ms`).
+The period must be from 1 ms to 1 s and the quota must be at least 1 ms as required by the kernel.
+Formatting produces the file format;
+the sub-microsecond part is truncated.
+
+The values are compared by quota and then by period; no limit is greater than any finite quota.
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct CgroupCpuMax {
+    /// Max. CPU time in each period.
+    pub quota: Limit<Duration>,
+    /// Accounting period.
+    pub period: Duration,
+}
+
+impl CgroupCpuMax {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 53;
+
+    /// Default period that the kernel uses.
+    pub const DEFAULT_PERIOD: Duration = Duration(StdDuration::from_millis(100));
+}
+
+impl Default for CgroupCpuMax {
+    fn default() -> Self {
+        Self {
+            quota: Limit::Unlimited,
+            period: Self::DEFAULT_PERIOD,
+        }
+    }
+}
+
+impl Display for CgroupCpuMax {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.quota {
+            Limit::Finite(quota) => write!(f, "{}", quota.0.as_micros())?,
+            Limit::Unlimited => f.write_str(MAX)?,
+        }
+        write!(f, " {}", self.period.0.as_micros())
+    }
+}
+
+impl FromStr for CgroupCpuMax {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (quota, period) = match other.split_once('/') {
+            Some((quota, period)) => (parse_max(quota, str::parse)?, period.parse()?),
+            None => {
+                let mut words = other.split_whitespace();
+                let quota = words.next().ok_or(DurationError)?;
+                let period = words.next().ok_or(DurationError)?;
+                if words.next().is_some() {
+                    return Err(DurationError);
+                }
+                (parse_max(quota, parse_micros)?, parse_micros(period)?)
+            }
+        };
+        if !(MIN_PERIOD..=MAX_PERIOD).contains(&period.0)
+            || matches!(quota, Limit::Finite(quota) if quota.0 < MIN_QUOTA)
+        {
+            return Err(DurationError);
+        }
+        Ok(Self { quota, period })
+    }
+}
+
+/**
+I/O limits of a single device in cgroup v2 `io.max` file format
+(`8:16 rbps=2097152 wbps=max riops=max wiops=120`).
+
+[`Limit::Unlimited`] means no limit and is written as `max`.
+
+Parsing accepts the device number followed by any subset of `rbps`, `wbps`, `riops` and `wiops` keys;
+missing keys are left unchanged when written; `max` means no limit.
+The missing keys are parsed as no limit.
+Byte rates accept any string accepted by [`Size`] (`rbps=2m`).
+Formatting produces the file format with all four keys.
+
+The values are compared field by field in declaration order; no limit is greater than any finite limit.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct CgroupIoMax {
+    /// Device major number.
+    pub major: u32,
+    /// Device minor number.
+    pub minor: u32,
+    /// Max. read bytes per second.
+    pub rbps: Limit<Size>,
+    /// Max. write bytes per second.
+    pub wbps: Limit<Size>,
+    /// Max. read operations per second.
+    pub riops: Limit<u64>,
+    /// Max. write operations per second.
+    pub wiops: Limit<u64>,
+}
+
+impl CgroupIoMax {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 127;
+}
+
+impl Display for CgroupIoMax {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}:{}", self.major, self.minor)?;
+        let limits = [
+            ("rbps", self.rbps.finite().map(|size| size.0)),
+            ("wbps", self.wbps.finite().map(|size| size.0)),
+            ("riops", self.riops.finite()),
+            ("wiops", self.wiops.finite()),
+        ];
+        for (key, limit) in limits {
+            match limit {
+                Some(limit) => write!(f, " {}={}", key, limit)?,
+                None => write!(f, " {}={}", key, MAX)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for CgroupIoMax {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let mut words = other.split_whitespace();
+        let (major, minor) = words
+            .next()
+            .and_then(|device| device.split_once(':'))
+            .ok_or(SizeError)?;
+        let mut limits = Self {
+            major: major.parse().map_err(|_| SizeError)?,
+            minor: minor.parse().map_err(|_| SizeError)?,
+            ..Default::default()
+        };
+        for word in words {
+            let (key, value) = word.split_once('=').ok_or(SizeError)?;
+            match key {
+                "rbps" => limits.rbps = parse_max(value, str::parse)?,
+                "wbps" => limits.wbps = parse_max(value, str::parse)?,
+                "riops" => limits.riops = parse_max(value, |s| parse_decimal(s).ok_or(SizeError))?,
+                "wiops" => limits.wiops = parse_max(value, |s| parse_decimal(s).ok_or(SizeError))?,
+                _ => return Err(SizeError),
+            }
+        }
+        Ok(limits)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CgroupMemoryLimit {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ CgroupMemoryLimit::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for CgroupMemoryLimit {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a cgroup memory limit")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CgroupCpuMax {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ CgroupCpuMax::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for CgroupCpuMax {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a cgroup CPU limit")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CgroupIoMax {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ CgroupIoMax::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for CgroupIoMax {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a cgroup I/O limit")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CgroupMemoryLimit {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "CgroupMemoryLimit".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::CgroupMemoryLimit".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Memory limit in cgroup v2 format: `max` or size with optional unit k, m, g or t (powers of 1024)",
+            r"^\s*(max|\+?[0-9]+\s*[kKmMgGtT]?)\s*$",
+            &["max", "536870912", "512m"],
+        )
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CgroupCpuMax {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "CgroupCpuMax".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::CgroupCpuMax".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "CPU bandwidth limit in cgroup v2 format: quota (or `max`) and period in microseconds separated by whitespace \
+            or durations separated by a slash; the period must be from 1 ms to 1 s and the quota must be at least 1 ms",
+            r"^(\s*(max|\+?[0-9]+\s*(ns|μs|ms|s|m|h|d)?)\s*/\s*\+?[0-9]+\s*(ns|μs|ms|s|m|h|d)?\s*|\s*(max|[0-9]+)\s+[0-9]+\s*)$",
+            &["50000 100000", "max 100000", "50ms/100ms"],
+        )
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CgroupIoMax {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "CgroupIoMax".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::CgroupIoMax".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "I/O limits in cgroup v2 format: device number followed by `rbps`, `wbps`, `riops` and `wiops` keys",
+            r"^\s*\+?[0-9]+:\+?[0-9]+(\s+((rbps|wbps)=(max|\+?[0-9]+[kKmMgGtT]?)|(riops|wiops)=(max|[0-9]+)))*\s*$",
+            &["8:16 rbps=2097152 wiops=120", "8:0 wbps=1m"],
+        )
+    }
+}
+
+/// Parses `max` as [`Limit::Unlimited`] and other values with `parse`.
+fn parse_max<T, E>(s: &str, parse: impl Fn(&str) -> Result<T, E>) -> Result<Limit<T>, E> {
+    let s = s.trim();
+    if s == MAX {
+        Ok(Limit::Unlimited)
+    } else {
+        parse(s).map(Limit::Finite)
+    }
+}
+
+fn parse_micros(s: &str) -> Result<Duration, DurationError> {
+    let micros = parse_decimal(s).ok_or(DurationError)?;
+    Ok(Duration(StdDuration::from_micros(micros)))
+}
+
+const MAX: &str = "max";
+const MIN_QUOTA: StdDuration = StdDuration::from_millis(1);
+const MIN_PERIOD: StdDuration = StdDuration::from_millis(1);
+const MAX_PERIOD: StdDuration = StdDuration::from_secs(1);
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use core::cmp::Ordering;
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_memory_limit() {
+        assert_eq!("max", CgroupMemoryLimit::MAX.to_string());
+        assert_eq!("max", CgroupMemoryLimit::default().to_string());
+        assert_eq!("536870912", memory_limit(512 << 20).to_string());
+        assert_eq!(CgroupMemoryLimit::MAX, "max".parse().unwrap());
+        assert_eq!(CgroupMemoryLimit::MAX, "max\n".parse().unwrap());
+        assert_eq!(memory_limit(512 << 20), "536870912\n".parse().unwrap());
+        assert_eq!(memory_limit(512 << 20), "512m".parse().unwrap());
+        assert_eq!(memory_limit(1 << 30), "1G".parse().unwrap());
+        assert_eq!(
+            CgroupMemoryLimit::MAX_STRING_LEN,
+            memory_limit(u64::MAX).to_string().len()
+        );
+        for s in ["", "Max", "maximum", "-1", "1x"] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<CgroupMemoryLimit>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpu_max() {
+        assert_eq!("max 100000", CgroupCpuMax::default().to_string());
+        assert_eq!("50000 100000", cpu_max(Some(50_000), 100_000).to_string());
+        assert_eq!(
+            cpu_max(Some(50_000), 100_000),
+            "50000 100000".parse().unwrap()
+        );
+        assert_eq!(
+            cpu_max(Some(50_000), 100_000),
+            "50000 100000\n".parse().unwrap()
+        );
+        assert_eq!(cpu_max(None, 100_000), "max 100000".parse().unwrap());
+        assert_eq!(
+            cpu_max(Some(50_000), 100_000),
+            "50ms/100ms".parse().unwrap()
+        );
+        assert_eq!(
+            cpu_max(Some(200_000), 100_000),
+            "200ms / 100ms".parse().unwrap()
+        );
+        assert_eq!(cpu_max(None, 1_000_000), "max/1s".parse().unwrap());
+        assert_eq!(cpu_max(Some(1000), 1000), "1000 1000".parse().unwrap());
+        let string = CgroupCpuMax {
+            quota: Limit::Finite(Duration(StdDuration::new(u64::MAX, 999_999_999))),
+            period: Duration(StdDuration::new(u64::MAX, 999_999_999)),
+        }
+        .to_string();
+        assert_eq!(CgroupCpuMax::MAX_STRING_LEN, string.len());
+        for s in [
+            "",
+            "max",
+            "50000",
+            "50000 100000 1",
+            "max max",
+            "50ms 100ms",
+            "50ms/",
+            "999 100000",
+            "1000 999",
+            "1000 1000001",
+            "1ms/2s",
+            "-1 100000",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<CgroupCpuMax>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_io_max() {
+        let expected = CgroupIoMax {
+            major: 8,
+            minor: 16,
+            rbps: Limit::Finite(Size(2097152)),
+            wbps: Limit::Unlimited,
+            riops: Limit::Unlimited,
+            wiops: Limit::Finite(120),
+        };
+        let string = "8:16 rbps=2097152 wbps=max riops=max wiops=120";
+        assert_eq!(string, expected.to_string());
+        assert_eq!(expected, string.parse().unwrap());
+        assert_eq!(expected, "8:16 wiops=120 rbps=2m\n".parse().unwrap());
+        assert_eq!(
+            CgroupIoMax {
+                major: 259,
+                minor: 0,
+                ..Default::default()
+            },
+            "259:0".parse().unwrap()
+        );
+        let string = CgroupIoMax {
+            major: u32::MAX,
+            minor: u32::MAX,
+            rbps: Limit::Finite(Size(u64::MAX)),
+            wbps: Limit::Finite(Size(u64::MAX)),
+            riops: Limit::Finite(u64::MAX),
+            wiops: Limit::Finite(u64::MAX),
+        }
+        .to_string();
+        assert_eq!(CgroupIoMax::MAX_STRING_LEN, string.len());
+        for s in [
+            "",
+            "8",
+            "8:",
+            "8:16 rbps",
+            "8:16 rbps=",
+            "8:16 riops=1k",
+            "8:16 xbps=1",
+            "8:16 rbps = 1",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<CgroupIoMax>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(memory_limit(0) < CgroupMemoryLimit::MAX);
+        assert!(memory_limit(u64::MAX) < CgroupMemoryLimit::MAX);
+        assert!(memory_limit(1) < memory_limit(2));
+        assert!(cpu_max(Some(u64::MAX), 100_000) < cpu_max(None, 100_000));
+        assert!(cpu_max(Some(1000), 1_000_000) < cpu_max(Some(2000), 1000));
+        assert!(cpu_max(None, 1000) < cpu_max(None, 2000));
+        let limited = CgroupIoMax {
+            major: 8,
+            minor: 16,
+            rbps: Limit::Finite(Size(u64::MAX)),
+            wbps: Limit::Unlimited,
+            riops: Limit::Finite(u64::MAX),
+            wiops: Limit::Unlimited,
+        };
+        let unlimited = CgroupIoMax {
+            major: 8,
+            minor: 16,
+            ..Default::default()
+        };
+        assert!(limited < unlimited);
+        assert!(
+            CgroupIoMax {
+                major: 9,
+                ..limited
+            } > unlimited
+        );
+        arbtest(|u| {
+            let a: CgroupMemoryLimit = u.arbitrary()?;
+            let b: CgroupMemoryLimit = u.arbitrary()?;
+            assert_eq!(a.0.cmp(&b.0), a.cmp(&b));
+            assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        use crate::str_schema::tests::check_schema;
+
+        check_schema::<CgroupMemoryLimit>(
+            &[
+                "0", "1", "9", "10", "max", "k", "M", "x", " ", "\t", "+", "-", ".",
+            ],
+            7,
+            |_| false,
+        );
+        // the bounds are not checked by the schema
+        check_schema::<CgroupCpuMax>(
+            &[
+                "0", "1", "9", "10", "max", "ms", "s", "x", "/", " ", "\t", "+", "-", ".",
+            ],
+            7,
+            |_| true,
+        );
+        check_schema::<CgroupIoMax>(
+            &[
+                "0", "1", "9", "10", ":", "=", "rbps", "wbps", "riops", "wiops", "max", "k", "x",
+                " ", "\t", "+", "-", ".",
+            ],
+            9,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: CgroupMemoryLimit = u.arbitrary()?;
+            let actual: CgroupMemoryLimit = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let expected: CgroupIoMax = u.arbitrary()?;
+            let actual: CgroupIoMax = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let quota = if u.arbitrary()? {
+                Some(u.int_in_range(1000..=u64::MAX)?)
+            } else {
+                None
+            };
+            let expected = cpu_max(quota, u.int_in_range(1000..=1_000_000)?);
+            let actual: CgroupCpuMax = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    fn memory_limit(size: u64) -> CgroupMemoryLimit {
+        CgroupMemoryLimit(Limit::Finite(Size(size)))
+    }
+
+    fn cpu_max(quota: Option<u64>, period: u64) -> CgroupCpuMax {
+        CgroupCpuMax {
+            quota: quota
+                .map(|quota| Duration(StdDuration::from_micros(quota)))
+                .into(),
+            period: Duration(StdDuration::from_micros(period)),
+        }
+    }
+}
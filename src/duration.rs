@@ -7,6 +7,8 @@ use core::ops::DerefMut;
 use core::str::FromStr;
 use core::time::Duration as StdDuration;
 
+use crate::ErrorKind;
+
 /**
 Exact duration.
 
@@ -14,7 +16,10 @@ The intended use is the configuration files where exact values are required,
 i.e. timeouts, cache max age, time-to-live etc.
 */
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(all(test, not(feature = "no_std")), derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
 #[repr(transparent)]
 pub struct Duration(pub StdDuration);
 
@@ -45,19 +50,21 @@ impl Display for Duration {
     }
 }
 
-impl FromStr for Duration {
-    type Err = DurationError;
-    fn from_str(other: &str) -> Result<Self, Self::Err> {
+impl Duration {
+    /// Parses the duration returning the reason of the failure.
+    pub(crate) fn parse(other: &str) -> Result<Self, ErrorKind> {
         let other = other.trim();
         match other.rfind(char::is_numeric) {
-            None => Err(DurationError),
+            None => Err(ErrorKind::NoNumber),
             Some(i) => {
-                let duration: u128 = other[..=i].parse().map_err(|_| DurationError)?;
+                let duration: u128 = ErrorKind::parse_integer(&other[..=i])?;
                 let unit = other[(i + 1)..].trim();
-                let factor = unit_to_factor(unit)? as u128;
-                let duration = duration * factor;
+                let factor = unit_to_factor(unit).map_err(|_| ErrorKind::UnknownUnit)? as u128;
+                let duration = duration.checked_mul(factor).ok_or(ErrorKind::Overflow)?;
+                let secs = u64::try_from(duration / NANOS_PER_SEC as u128)
+                    .map_err(|_| ErrorKind::Overflow)?;
                 Ok(Self(StdDuration::new(
-                    (duration / NANOS_PER_SEC as u128) as u64,
+                    secs,
                     (duration % NANOS_PER_SEC as u128) as u32,
                 )))
             }
@@ -65,6 +72,13 @@ impl FromStr for Duration {
     }
 }
 
+impl FromStr for Duration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        Self::parse(other).map_err(|_| DurationError)
+    }
+}
+
 impl From<StdDuration> for Duration {
     fn from(other: StdDuration) -> Self {
         Self(other)
@@ -117,6 +131,48 @@ impl Display for DurationError {
 #[cfg(not(feature = "no_std"))]
 impl std::error::Error for DurationError {}
 
+/// Error of conversion between [`Duration`] and the duration types of other libraries.
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "jiff",
+    feature = "postgres-types"
+))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationConversionError {
+    /// The duration is negative.
+    Negative,
+    /// The duration can not be represented by the target type.
+    OutOfRange,
+    /// The duration is more precise than the target type.
+    Inexact,
+}
+
+#[cfg(any(
+    feature = "chrono",
+    feature = "time",
+    feature = "jiff",
+    feature = "postgres-types"
+))]
+impl Display for DurationConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Negative => f.write_str("duration is negative"),
+            Self::OutOfRange => f.write_str("duration is out of range"),
+            Self::Inexact => f.write_str("duration is too precise"),
+        }
+    }
+}
+
+#[cfg(any(
+    all(
+        any(feature = "chrono", feature = "time", feature = "jiff"),
+        not(feature = "no_std")
+    ),
+    feature = "postgres-types"
+))]
+impl std::error::Error for DurationConversionError {}
+
 const UNITS: [(NonZeroU16, &str); 6] = [
     (unsafe { NonZeroU16::new_unchecked(1000) }, "μs"),
     (unsafe { NonZeroU16::new_unchecked(1000) }, "ms"),
@@ -126,6 +182,10 @@ const UNITS: [(NonZeroU16, &str); 6] = [
     (unsafe { NonZeroU16::new_unchecked(24) }, "d"),
 ];
 
+/// Supported units in error messages.
+#[cfg(any(feature = "serde", feature = "clap"))]
+pub(crate) const DURATION_UNITS: &str = "ns, μs, ms, s, m, h or d";
+
 const NANOS_PER_SEC: u32 = 1_000_000_000_u32;
 
 #[cfg(all(test, not(feature = "no_std")))]
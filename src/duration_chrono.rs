@@ -0,0 +1,66 @@
+use crate::Duration;
+use crate::DurationConversionError;
+
+impl TryFrom<Duration> for chrono::TimeDelta {
+    type Error = DurationConversionError;
+    fn try_from(other: Duration) -> Result<Self, Self::Error> {
+        Self::from_std(other.0).map_err(|_| DurationConversionError::OutOfRange)
+    }
+}
+
+impl TryFrom<chrono::TimeDelta> for Duration {
+    type Error = DurationConversionError;
+    fn try_from(other: chrono::TimeDelta) -> Result<Self, Self::Error> {
+        if other < chrono::TimeDelta::zero() {
+            return Err(DurationConversionError::Negative);
+        }
+        other
+            .to_std()
+            .map(Self)
+            .map_err(|_| DurationConversionError::OutOfRange)
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use core::time::Duration as StdDuration;
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(DurationConversionError::Negative),
+            Duration::try_from(chrono::TimeDelta::nanoseconds(-1))
+        );
+        assert_eq!(
+            Err(DurationConversionError::OutOfRange),
+            chrono::TimeDelta::try_from(Duration(StdDuration::MAX))
+        );
+        assert_eq!(
+            Ok(chrono::TimeDelta::MAX),
+            Duration::try_from(chrono::TimeDelta::MAX).and_then(chrono::TimeDelta::try_from)
+        );
+    }
+
+    #[test]
+    fn test_conversion_symmetry() {
+        arbtest(|u| {
+            let expected: Duration = u.arbitrary()?;
+            if let Ok(delta) = chrono::TimeDelta::try_from(expected) {
+                assert_eq!(Ok(expected), Duration::try_from(delta));
+            }
+            let secs = u.int_in_range(0..=chrono::TimeDelta::MAX.num_seconds() - 1)?;
+            let expected = Duration(StdDuration::new(
+                secs as u64,
+                u.int_in_range(0..=999_999_999)?,
+            ));
+            let delta = chrono::TimeDelta::try_from(expected).unwrap();
+            assert_eq!(Ok(expected), Duration::try_from(delta));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,271 @@
+use core::ops::Bound;
+use core::ops::RangeBounds;
+use core::time::Duration as StdDuration;
+use std::ffi::OsStr;
+
+use crate::Duration;
+use crate::ExpectedUnits;
+use crate::DURATION_UNITS;
+
+/**
+Command line argument parser for [`Duration`].
+
+`clap::value_parser!(Duration)` returns this parser that accepts any duration.
+Use [`range`](DurationValueParser::range) to restrict the accepted values.
+
+```rust
+use clap::Parser;
+use core::time::Duration as StdDuration;
+use human_units::{Duration, DurationValueParser};
+
+#[derive(Parser)]
+struct Args {
+    #[arg(
+        long,
+        value_name = DurationValueParser::VALUE_NAME,
+        help = DurationValueParser::HELP,
+        value_parser = DurationValueParser::new().range(..=Duration(StdDuration::from_secs(3600))),
+    )]
+    timeout: Duration,
+}
+
+let args = Args::try_parse_from(["test", "--timeout", "30s"]).unwrap();
+assert_eq!(Duration(StdDuration::from_secs(30)), args.timeout);
+assert!(Args::try_parse_from(["test", "--timeout", "2h"]).is_err());
+```
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DurationValueParser {
+    min: StdDuration,
+    max: StdDuration,
+}
+
+impl DurationValueParser {
+    /// Value name that is shown in the usage.
+    pub const VALUE_NAME: &'static str = "DURATION";
+
+    /// Help snippet that describes the format.
+    pub const HELP: &'static str =
+        "Duration with optional unit ns, μs, ms, s, m, h or d (seconds by default), e.g. 30s";
+
+    /// Creates the parser that accepts any duration.
+    pub const fn new() -> Self {
+        Self {
+            min: StdDuration::ZERO,
+            max: StdDuration::MAX,
+        }
+    }
+
+    /// Accepts only the durations from the `range`.
+    ///
+    /// The parser rejects any duration if the range is empty.
+    pub fn range<B: RangeBounds<Duration>>(self, range: B) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(min) => Some(min.0),
+            Bound::Excluded(min) => min.0.checked_add(ONE_NANOSECOND),
+            Bound::Unbounded => Some(StdDuration::ZERO),
+        };
+        let max = match range.end_bound() {
+            Bound::Included(max) => Some(max.0),
+            Bound::Excluded(max) => max.0.checked_sub(ONE_NANOSECOND),
+            Bound::Unbounded => Some(StdDuration::MAX),
+        };
+        match (min, max) {
+            (Some(min), Some(max)) if min <= max => Self { min, max },
+            _ => Self::EMPTY,
+        }
+    }
+
+    /// The parser that rejects any duration.
+    const EMPTY: Self = Self {
+        min: ONE_NANOSECOND,
+        max: StdDuration::ZERO,
+    };
+}
+
+impl Default for DurationValueParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl clap::builder::TypedValueParser for DurationValueParser {
+    type Value = Duration;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = crate::clap_str(cmd, value)?;
+        let duration = Duration::parse(value).map_err(|reason| {
+            crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                ExpectedUnits {
+                    what: "a duration",
+                    units: DURATION_UNITS,
+                    reason,
+                },
+            )
+        })?;
+        if *self == Self::EMPTY {
+            return Err(crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                "a duration from an empty range",
+            ));
+        }
+        if !(self.min..=self.max).contains(&duration.0) {
+            return Err(crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                format_args!(
+                    "a duration from {} to {}",
+                    Duration(self.min),
+                    Duration(self.max)
+                ),
+            ));
+        }
+        Ok(duration)
+    }
+}
+
+impl clap::builder::ValueParserFactory for Duration {
+    type Parser = DurationValueParser;
+
+    fn value_parser() -> Self::Parser {
+        DurationValueParser::new()
+    }
+}
+
+const ONE_NANOSECOND: StdDuration = StdDuration::from_nanos(1);
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use clap::builder::TypedValueParser;
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser, Debug)]
+    struct Args {
+        #[arg(
+            long,
+            value_name = DurationValueParser::VALUE_NAME,
+            help = DurationValueParser::HELP,
+            value_parser = clap::value_parser!(Duration),
+        )]
+        timeout: Option<Duration>,
+        #[arg(
+            long,
+            value_parser = DurationValueParser::new()
+                .range(Duration(StdDuration::from_secs(1))..=Duration(StdDuration::from_secs(3600))),
+        )]
+        interval: Option<Duration>,
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = Args::try_parse_from(["test", "--timeout", "1m", "--interval", "1h"]).unwrap();
+        assert_eq!(Some(Duration(StdDuration::from_secs(60))), args.timeout);
+        assert_eq!(Some(Duration(StdDuration::from_secs(3600))), args.interval);
+        let args = Args::try_parse_from(["test", "--interval", "1000ms"]).unwrap();
+        assert_eq!(Some(Duration(StdDuration::from_secs(1))), args.interval);
+    }
+
+    #[test]
+    fn test_errors() {
+        for (args, error) in [
+            (
+                ["test", "--timeout", "5 minutes"],
+                "error: invalid value '5 minutes' for '--timeout <DURATION>': expected a duration with optional unit ns, μs, ms, s, m, h or d (unknown unit)\n",
+            ),
+            (
+                ["test", "--timeout", "1.5s"],
+                "error: invalid value '1.5s' for '--timeout <DURATION>': expected a duration with optional unit ns, μs, ms, s, m, h or d (not a non-negative integer)\n",
+            ),
+            (
+                ["test", "--interval", "999ms"],
+                "error: invalid value '999ms' for '--interval <INTERVAL>': expected a duration from 1s to 1h\n",
+            ),
+            (
+                ["test", "--interval", "2h"],
+                "error: invalid value '2h' for '--interval <INTERVAL>': expected a duration from 1s to 1h\n",
+            ),
+        ] {
+            let actual = Args::try_parse_from(args).unwrap_err();
+            assert_eq!(clap::error::ErrorKind::ValueValidation, actual.kind());
+            let actual = actual.to_string();
+            assert!(actual.starts_with(error), "actual = `{}`", actual);
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let second = Duration(StdDuration::from_secs(1));
+        assert_eq!(
+            DurationValueParser::new(),
+            DurationValueParser::new().range(..)
+        );
+        assert_eq!(
+            DurationValueParser {
+                min: StdDuration::from_nanos(1_000_000_001),
+                max: StdDuration::MAX
+            },
+            DurationValueParser::new().range((Bound::Excluded(second), Bound::Unbounded))
+        );
+        assert_eq!(
+            DurationValueParser {
+                min: StdDuration::ZERO,
+                max: StdDuration::from_nanos(999_999_999)
+            },
+            DurationValueParser::new().range(..second)
+        );
+        let cmd = clap::Command::new("test");
+        for (parser, value) in [
+            (
+                DurationValueParser::new().range(..Duration(StdDuration::ZERO)),
+                "0",
+            ),
+            (
+                DurationValueParser::new().range((
+                    Bound::Excluded(Duration(StdDuration::MAX)),
+                    Bound::Unbounded,
+                )),
+                "18446744073709551615",
+            ),
+            (DurationValueParser::new().range(second..second), "1s"),
+        ] {
+            let error = parser
+                .parse_ref(&cmd, None, OsStr::new(value))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                error.contains("expected a duration from an empty range"),
+                "value = `{}`, error = `{}`",
+                value,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_help() {
+        let help = Args::try_parse_from(["test", "--help"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            help.lines()
+                .any(|line| line.contains("--timeout <DURATION>")
+                    && line.ends_with(DurationValueParser::HELP)),
+            "help = `{}`",
+            help
+        );
+    }
+}
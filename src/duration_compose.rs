@@ -0,0 +1,347 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Exact duration in [Compose file](https://github.com/compose-spec/compose-spec/blob/main/11-extension.md#specifying-durations)
+format (`interval: 1m30s`).
+
+Parsing follows Go's `time.ParseDuration`:
+components such as `1h`, `1m30s` or `1.5s` are summed up in any order,
+units are `ns`, `us` (`µs`, `μs`), `ms`, `s`, `m` and `h`,
+`0` is the only value without a unit,
+fractions are truncated to the whole number of nanoseconds,
+and durations that are negative or longer than `i64::MAX` nanoseconds are rejected.
+
+Formatting produces the same output as Go's `time.Duration.String` (`1h30m0s`, `1.5ms`).
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct ComposeDuration(pub Duration);
+
+impl ComposeDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 33;
+}
+
+impl Display for ComposeDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let nanoseconds = self.0 .0.as_nanos();
+        if nanoseconds == 0 {
+            f.write_str("0s")
+        } else if nanoseconds < NANOS_PER_MICRO {
+            write!(f, "{}ns", nanoseconds)
+        } else if nanoseconds < NANOS_PER_MILLI {
+            write_fraction(f, nanoseconds, 3)?;
+            f.write_str("µs")
+        } else if nanoseconds < NANOS_PER_SECOND {
+            write_fraction(f, nanoseconds, 6)?;
+            f.write_str("ms")
+        } else {
+            let seconds = self.0 .0.as_secs();
+            if seconds >= 60 * 60 {
+                write!(f, "{}h", seconds / (60 * 60))?;
+            }
+            if seconds >= 60 {
+                write!(f, "{}m", seconds / 60 % 60)?;
+            }
+            write_fraction(
+                f,
+                (seconds % 60) as u128 * NANOS_PER_SECOND + nanoseconds % NANOS_PER_SECOND,
+                9,
+            )?;
+            f.write_str("s")
+        }
+    }
+}
+
+impl FromStr for ComposeDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (negative, mut rest) = match other.as_bytes().first() {
+            Some(b'-') => (true, &other[1..]),
+            Some(b'+') => (false, &other[1..]),
+            _ => (false, other),
+        };
+        if rest == "0" {
+            return Ok(Self::default());
+        }
+        if rest.is_empty() {
+            return Err(DurationError);
+        }
+        let mut total: u128 = 0;
+        while !rest.is_empty() {
+            let integer_len = rest
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let integer = &rest[..integer_len];
+            rest = &rest[integer_len..];
+            let mut fraction = "";
+            if let Some(s) = rest.strip_prefix('.') {
+                let fraction_len = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+                fraction = &s[..fraction_len];
+                rest = &s[fraction_len..];
+            }
+            if integer.is_empty() && fraction.is_empty() {
+                return Err(DurationError);
+            }
+            let unit_len = rest
+                .find(|ch: char| ch == '.' || ch.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (unit, tail) = rest.split_at(unit_len);
+            rest = tail;
+            let factor = UNITS
+                .iter()
+                .find(|(u, _)| *u == unit)
+                .ok_or(DurationError)?
+                .1;
+            let integer: u128 = if integer.is_empty() {
+                0
+            } else {
+                integer.parse().map_err(|_| DurationError)?
+            };
+            total = integer
+                .checked_mul(factor)
+                .and_then(|x| x.checked_add(total))
+                .ok_or(DurationError)?;
+            // digits that do not affect the result are ignored
+            let fraction = &fraction[..fraction.len().min(MAX_FRACTION_DIGITS)];
+            if !fraction.is_empty() {
+                let numerator: u128 = fraction.parse().map_err(|_| DurationError)?;
+                total += numerator * factor / 10_u128.pow(fraction.len() as u32);
+            }
+            if total > i64::MAX as u128 {
+                return Err(DurationError);
+            }
+        }
+        if negative && total != 0 {
+            return Err(DurationError);
+        }
+        Ok(Self(Duration(StdDuration::from_nanos(total as u64))))
+    }
+}
+
+impl From<Duration> for ComposeDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<ComposeDuration> for Duration {
+    fn from(other: ComposeDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for ComposeDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ComposeDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ComposeDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ ComposeDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for ComposeDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a Compose duration")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ComposeDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "ComposeDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::ComposeDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Duration in Compose file format with units ns, us, ms, s, m and h; the components are summed up",
+            r"^[+-]?(0|(([0-9]+(\.[0-9]*)?|\.[0-9]+)(ns|us|µs|μs|ms|s|m|h))+)$",
+            &["1m30s", "1.5s", "10ms", "0"],
+        )
+    }
+}
+
+/// Writes `n / 10^precision` omitting trailing zeros in the fractional part.
+fn write_fraction(f: &mut core::fmt::Formatter, n: u128, mut precision: u32) -> core::fmt::Result {
+    let scale = 10_u128.pow(precision);
+    let mut fraction = n % scale;
+    write!(f, "{}", n / scale)?;
+    if fraction != 0 {
+        while fraction % 10 == 0 {
+            fraction /= 10;
+            precision -= 1;
+        }
+        write!(f, ".{:0width$}", fraction, width = precision as usize)?;
+    }
+    Ok(())
+}
+
+const NANOS_PER_MICRO: u128 = 1000;
+const NANOS_PER_MILLI: u128 = 1000 * NANOS_PER_MICRO;
+const NANOS_PER_SECOND: u128 = 1000 * NANOS_PER_MILLI;
+
+// Enough digits to represent one nanosecond of the largest unit.
+const MAX_FRACTION_DIGITS: usize = 20;
+
+const UNITS: [(&str, u128); 8] = [
+    ("ns", 1),
+    ("us", NANOS_PER_MICRO),
+    ("µs", NANOS_PER_MICRO),
+    ("μs", NANOS_PER_MICRO),
+    ("ms", NANOS_PER_MILLI),
+    ("s", NANOS_PER_SECOND),
+    ("m", 60 * NANOS_PER_SECOND),
+    ("h", 60 * 60 * NANOS_PER_SECOND),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0s", compose_nanos(0).to_string());
+        assert_eq!("1ns", compose_nanos(1).to_string());
+        assert_eq!("1µs", compose_nanos(1000).to_string());
+        assert_eq!("1.5µs", compose_nanos(1500).to_string());
+        assert_eq!("10ms", compose_nanos(10_000_000).to_string());
+        assert_eq!("1.000001ms", compose_nanos(1_000_001).to_string());
+        assert_eq!("40s", compose_nanos(40_000_000_000).to_string());
+        assert_eq!("1.5s", compose_nanos(1_500_000_000).to_string());
+        assert_eq!("1m30s", compose_nanos(90_000_000_000).to_string());
+        assert_eq!("1h0m0s", compose_nanos(3_600_000_000_000).to_string());
+        assert_eq!("1h5m30.02s", compose_nanos(3_930_020_000_000).to_string());
+        assert_eq!(
+            "2562047h47m16.854775807s",
+            compose_nanos(i64::MAX as u128).to_string()
+        );
+        let string = ComposeDuration(Duration(StdDuration::new(
+            5124095576030430 * 60 * 60 + 60 * 60 - 1,
+            999_999_999,
+        )))
+        .to_string();
+        assert_eq!(
+            ComposeDuration::MAX_STRING_LEN,
+            string.len(),
+            "string = `{}`",
+            string
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(compose_nanos(0), "0".parse().unwrap());
+        assert_eq!(compose_nanos(0), "-0".parse().unwrap());
+        assert_eq!(compose_nanos(0), "0s".parse().unwrap());
+        assert_eq!(compose_nanos(10_000_000), "10ms".parse().unwrap());
+        assert_eq!(compose_nanos(40_000_000_000), "40s".parse().unwrap());
+        assert_eq!(compose_nanos(90_000_000_000), "1m30s".parse().unwrap());
+        assert_eq!(compose_nanos(90_000_000_000), "30s1m".parse().unwrap());
+        assert_eq!(compose_nanos(90_000_000_000), "+1.5m".parse().unwrap());
+        assert_eq!(
+            compose_nanos(3_930_020_000_000),
+            "1h5m30s20ms".parse().unwrap()
+        );
+        assert_eq!(compose_nanos(1000), "1us".parse().unwrap());
+        assert_eq!(compose_nanos(1000), "1µs".parse().unwrap());
+        assert_eq!(compose_nanos(1000), "1μs".parse().unwrap());
+        assert_eq!(compose_nanos(1_000_000_000), "1.s".parse().unwrap());
+        assert_eq!(compose_nanos(500_000_000), ".5s".parse().unwrap());
+        assert_eq!(compose_nanos(1), "1.9ns".parse().unwrap());
+        assert_eq!(
+            compose_nanos(1_199_999_999_999),
+            "0.333333333333333333333333h".parse().unwrap()
+        );
+        assert_eq!(
+            compose_nanos(i64::MAX as u128),
+            "2562047h47m16.854775807s".parse().unwrap()
+        );
+        for s in [
+            "",
+            "1",
+            "s",
+            ".s",
+            "1d",
+            "1 s",
+            " 1s",
+            "1s ",
+            "1S",
+            "1sec",
+            "-1s",
+            "1.2.3s",
+            "2562047h47m16.854775808s",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<ComposeDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<ComposeDuration>(
+            &[
+                "0", "1", "9", "10", "ns", "us", "µs", "μs", "ms", "s", "m", "h", "d", " ", "+",
+                "-", ".",
+            ],
+            5,
+            |s| s.contains('-'),
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected = compose_nanos(u.int_in_range(0..=i64::MAX as u128)?);
+            let string = expected.to_string();
+            let actual: ComposeDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn compose_nanos(nanoseconds: u128) -> ComposeDuration {
+        ComposeDuration(Duration(StdDuration::from_nanos(nanoseconds as u64)))
+    }
+}
@@ -1,16 +1,25 @@
 use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Write;
+use core::str::FromStr;
 
 use crate::Buffer;
 use crate::Duration;
+use crate::DurationError;
 
 /**
 Approximate duration that includes unit, integral and fractional parts as fields.
 
 This type is useful when you need custom formatting of the output,
 i.e. colors, locale-specific units etc.
+
+Parsing accepts the output of `Display` implementation (`1.5 h`).
+
+In human-readable serde formats the value is serialized as a string (`"1.5 h"`)
+and in binary formats as a struct;
+deserialization also accepts `{integer, fraction, unit}` map.
 */
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct FormattedDuration {
     /// Duration unit.
     pub unit: &'static str,
@@ -34,8 +43,87 @@ impl Display for FormattedDuration {
     }
 }
 
+impl FromStr for FormattedDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (integer, fraction, unit) =
+            crate::parse_formatted(other, &UNITS).ok_or(DurationError)?;
+        Self::from_parts(integer, fraction, unit).ok_or(DurationError)
+    }
+}
+
+impl FormattedDuration {
+    fn from_parts(integer: u64, fraction: u8, unit: &'static str) -> Option<Self> {
+        if integer > MAX_INTEGER || fraction > 9 {
+            return None;
+        }
+        Some(Self {
+            unit,
+            integer,
+            fraction,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::FormattedParts for FormattedDuration {
+    const NAME: &'static str = "FormattedDuration";
+    const UNITS: &'static [&'static str] = &UNITS;
+
+    fn from_parts(integer: u64, fraction: u8, unit: &'static str) -> Option<Self> {
+        Self::from_parts(integer, fraction, unit)
+    }
+
+    fn to_parts(&self) -> (u64, u8, &'static str) {
+        (self.integer, self.fraction, self.unit)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FormattedDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_formatted::<MAX_LEN, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for FormattedDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_formatted(d)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for FormattedDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "FormattedDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::FormattedDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::formatted_schema(
+            "Approximate duration with unit ns, μs, ms, s, m, h or d",
+            r"^[0-9]+(\.[1-9])? (ns|μs|ms|s|m|h|d)$",
+            MAX_INTEGER,
+            &UNITS,
+            &["1.5 h", "500 ms"],
+        )
+    }
+}
+
 const MAX_LEN: usize = 21;
 const MAX_POWOF10: u64 = 100000000000000;
+const MAX_INTEGER: u64 = 213503982334601;
+const UNITS: [&str; 7] = ["ns", "μs", "ms", "s", "m", "h", "d"];
 
 /**
 This trait adds [`format_duration`](FormatDuration::format_duration) method to
@@ -106,13 +194,22 @@ impl FormatDuration for Duration {
     }
 }
 
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a> arbitrary::Arbitrary<'a> for FormattedDuration {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            unit: *u.choose(&UNITS[..])?,
+            integer: u.int_in_range(0..=MAX_INTEGER)?,
+            fraction: u.int_in_range(0..=9)?,
+        })
+    }
+}
+
 #[cfg(all(test, not(feature = "no_std")))]
 mod tests {
     #![allow(clippy::panic)]
     use core::time::Duration;
 
-    use arbitrary::Arbitrary;
-    use arbitrary::Unstructured;
     use arbtest::arbtest;
 
     use super::*;
@@ -241,13 +338,137 @@ mod tests {
         });
     }
 
-    impl<'a> Arbitrary<'a> for FormattedDuration {
-        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self, arbitrary::Error> {
-            Ok(Self {
-                unit: *u.choose(&UNITS[..])?,
-                integer: u.int_in_range(0..=MAX_INTEGER)?,
-                fraction: u.int_in_range(0..=9)?,
-            })
+    #[test]
+    fn test_parse_formatted() {
+        assert_eq!(
+            FormattedDuration {
+                unit: "h",
+                integer: 1,
+                fraction: 5
+            },
+            "1.5 h".parse().unwrap()
+        );
+        assert_eq!(
+            FormattedDuration {
+                unit: "s",
+                integer: 0,
+                fraction: 0
+            },
+            "0 s".parse().unwrap()
+        );
+        assert_eq!(
+            FormattedDuration {
+                unit: "μs",
+                integer: 999,
+                fraction: 0
+            },
+            "999 μs".parse().unwrap()
+        );
+        assert_eq!(
+            FormattedDuration {
+                unit: "d",
+                integer: 213503982334601,
+                fraction: 9
+            },
+            "213503982334601.9 d".parse().unwrap()
+        );
+        for s in [
+            "",
+            "1",
+            "1 ",
+            " s",
+            "1s",
+            "1  s",
+            "1 us",
+            "1.0 s",
+            "1.10 s",
+            "1. s",
+            ".5 s",
+            "-1 s",
+            "213503982334602 d",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<FormattedDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+        arbtest(|u| {
+            let expected: FormattedDuration = u.arbitrary()?;
+            let actual: FormattedDuration = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_formatted() {
+        let expected = FormattedDuration {
+            unit: "h",
+            integer: 1,
+            fraction: 5,
+        };
+        let string = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            serde_json::to_string(&expected.to_string()).unwrap(),
+            string
+        );
+        assert_eq!(expected, serde_json::from_str(&string).unwrap());
+        assert_eq!(
+            expected,
+            serde_json::from_str(r#"{"integer":1,"fraction":5,"unit":"h"}"#).unwrap()
+        );
+        for s in [
+            r#"{"integer":1,"fraction":5}"#,
+            r#"{"integer":1,"fraction":10,"unit":"h"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"x"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"h","x":1}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<FormattedDuration>(s).is_err(),
+                "s = `{}`",
+                s
+            );
+        }
+        arbtest(|u| {
+            let expected: FormattedDuration = u.arbitrary()?;
+            let bytes = bincode::serialize(&expected).unwrap();
+            let actual = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            let string = serde_json::to_string(&expected).unwrap();
+            let actual = serde_json::from_str(&string).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(all(feature = "serde", feature = "schemars"))]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<FormattedDuration>(
+            &[
+                "0", "1", "9", "10", "ns", "μs", "us", "ms", "s", "m", "h", "d", " ", "+", ".",
+            ],
+            14,
+            |_| false,
+        );
+        let schema = serde_json::to_value(schemars::schema_for!(FormattedDuration)).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for s in [
+            r#"{"integer":1,"fraction":5,"unit":"h"}"#,
+            r#"{"integer":1,"fraction":5}"#,
+            r#"{"integer":1,"fraction":10,"unit":"h"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"x"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"h","x":1}"#,
+        ] {
+            assert_eq!(
+                serde_json::from_str::<FormattedDuration>(s).is_ok(),
+                validator.is_valid(&serde_json::from_str(s).unwrap()),
+                "s = `{}`",
+                s
+            );
         }
     }
 
@@ -263,7 +484,4 @@ mod tests {
             _ => panic!("unknown unit `{}`", unit),
         }
     }
-
-    const UNITS: [&str; 7] = ["ns", "μs", "ms", "s", "m", "h", "d"];
-    const MAX_INTEGER: u64 = 213503982334601;
 }
@@ -0,0 +1,502 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::Chars;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Duration in [humantime](https://docs.rs/humantime) format (`15days 2min 2s`, `2years`, `1M`).
+
+Parsing accepts the same strings and produces the same values as `humantime::parse_duration`:
+components such as `2h`, `37min` or `4.2s` are summed up in any order,
+whitespace is allowed anywhere between digits and units,
+units are `nanos`/`nsec`/`ns`, `usec`/`us`/`µs`, `millis`/`msec`/`ms`, `seconds`/`second`/`secs`/`sec`/`s`,
+`minutes`/`minute`/`mins`/`min`/`m`, `hours`/`hour`/`hrs`/`hr`/`h`, `days`/`day`/`d`,
+`weeks`/`week`/`wks`/`wk`/`w`, `months`/`month`/`M` (30.44 days) and
+`years`/`year`/`yrs`/`yr`/`y` (365.25 days),
+`0` is the only value without a unit,
+and fractions that are not a whole number of nanoseconds are rejected.
+
+Formatting produces the same output as `humantime::format_duration` (`2h 37m`, `1year 15days`).
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct HumantimeDuration(pub Duration);
+
+impl HumantimeDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 63;
+}
+
+impl Display for HumantimeDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let seconds = self.0 .0.as_secs();
+        let nanoseconds = self.0 .0.subsec_nanos();
+        if seconds == 0 && nanoseconds == 0 {
+            return f.write_str("0s");
+        }
+        let year_seconds = seconds % SECONDS_PER_YEAR;
+        let month_seconds = year_seconds % SECONDS_PER_MONTH;
+        let day_seconds = month_seconds % SECONDS_PER_DAY;
+        let components = [
+            (seconds / SECONDS_PER_YEAR, "year", true),
+            (year_seconds / SECONDS_PER_MONTH, "month", true),
+            (month_seconds / SECONDS_PER_DAY, "day", true),
+            (day_seconds / 3600, "h", false),
+            (day_seconds % 3600 / 60, "m", false),
+            (day_seconds % 60, "s", false),
+            ((nanoseconds / 1_000_000) as u64, "ms", false),
+            ((nanoseconds / 1000 % 1000) as u64, "us", false),
+            ((nanoseconds % 1000) as u64, "ns", false),
+        ];
+        let mut separator = "";
+        for (n, unit, plural) in components {
+            if n != 0 {
+                write!(f, "{}{}{}", separator, n, unit)?;
+                if plural && n > 1 {
+                    f.write_str("s")?;
+                }
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for HumantimeDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        if other == "0" {
+            return Ok(Self::default());
+        }
+        let parser = Parser {
+            iter: other.chars(),
+            src: other,
+        };
+        Ok(Self(Duration(parser.parse()?)))
+    }
+}
+
+impl From<Duration> for HumantimeDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<HumantimeDuration> for Duration {
+    fn from(other: HumantimeDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for HumantimeDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for HumantimeDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HumantimeDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ HumantimeDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for HumantimeDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a humantime duration")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for HumantimeDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "HumantimeDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::HumantimeDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Duration in humantime format; the components are summed up",
+            r"^(0|\s*([0-9][0-9\s]*((nanos|nsec|ns|usec|us|µs|millis|msec|ms|seconds|second|secs|sec|s|minutes|minute|mins|min|m|hours|hour|hrs|hr|h|days|day|d|weeks|week|wks|wk|w|months|month|M|years|year|yrs|yr|y)|\.\s*[0-9][0-9\s]*(usec|us|µs|millis|msec|ms|seconds|second|secs|sec|s|minutes|minute|mins|min|m|hours|hour|hrs|hr|h|days|day|d|weeks|week|wks|wk|w|months|month|M|years|year|yrs|yr|y))\s*)+)$",
+            &["15days 2min 2s", "2h 37min", "1.5s"],
+        )
+    }
+}
+
+/// A port of humantime's parser that keeps all of its quirks,
+/// i.e. digits separated by whitespace (`1 2s` is 12 seconds)
+/// and overflow checks on intermediate values.
+struct Parser<'a> {
+    iter: Chars<'a>,
+    src: &'a str,
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> Result<StdDuration, DurationError> {
+        let mut n = self.parse_first_char()?.ok_or(DurationError)?;
+        let mut out = StdDuration::from_secs(0);
+        'outer: loop {
+            let mut fraction = None;
+            let mut off = self.off();
+            while let Some(c) = self.iter.next() {
+                match c {
+                    '0'..='9' => {
+                        n = n
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(c as u64 - '0' as u64))
+                            .ok_or(DurationError)?;
+                    }
+                    c if c.is_whitespace() => {}
+                    'a'..='z' | 'A'..='Z' | 'µ' => break,
+                    '.' => {
+                        fraction = Some(self.parse_fraction(&mut off)?);
+                        break;
+                    }
+                    _ => return Err(DurationError),
+                }
+                off = self.off();
+            }
+            let start = off;
+            let mut off = self.off();
+            while let Some(c) = self.iter.next() {
+                match c {
+                    '0'..='9' => {
+                        add_unit(&self.src[start..off], n, fraction, &mut out)?;
+                        n = c as u64 - '0' as u64;
+                        continue 'outer;
+                    }
+                    c if c.is_whitespace() => break,
+                    'a'..='z' | 'A'..='Z' | 'µ' => {}
+                    _ => return Err(DurationError),
+                }
+                off = self.off();
+            }
+            add_unit(&self.src[start..off], n, fraction, &mut out)?;
+            n = match self.parse_first_char()? {
+                Some(n) => n,
+                None => return Ok(out),
+            };
+        }
+    }
+
+    fn parse_first_char(&mut self) -> Result<Option<u64>, DurationError> {
+        for c in self.iter.by_ref() {
+            match c {
+                '0'..='9' => return Ok(Some(c as u64 - '0' as u64)),
+                c if c.is_whitespace() => continue,
+                _ => return Err(DurationError),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the numerator and the denominator.
+    fn parse_fraction(&mut self, off: &mut usize) -> Result<(u64, u64), DurationError> {
+        let mut numerator: u64 = 0;
+        let mut denominator: u64 = 1;
+        while let Some(c) = self.iter.next() {
+            match c {
+                '0'..='9' => {
+                    denominator = denominator.checked_mul(10).ok_or(DurationError)?;
+                    numerator = numerator
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(c as u64 - '0' as u64))
+                        .ok_or(DurationError)?;
+                }
+                c if c.is_whitespace() => {}
+                'a'..='z' | 'A'..='Z' | 'µ' => break,
+                _ => return Err(DurationError),
+            }
+            *off = self.off();
+        }
+        if denominator == 1 {
+            // no digits after the decimal separator
+            return Err(DurationError);
+        }
+        Ok((numerator, denominator))
+    }
+
+    fn off(&self) -> usize {
+        self.src.len() - self.iter.as_str().len()
+    }
+}
+
+fn add_unit(
+    unit: &str,
+    n: u64,
+    fraction: Option<(u64, u64)>,
+    out: &mut StdDuration,
+) -> Result<(), DurationError> {
+    let (seconds_factor, nanoseconds_factor) = UNITS
+        .iter()
+        .find(|(names, ..)| names.contains(&unit))
+        .map(|(_, s, ns)| (*s, *ns))
+        .ok_or(DurationError)?;
+    // the integral part
+    let seconds = n.checked_mul(seconds_factor).ok_or(DurationError)?;
+    let nanoseconds = n.checked_mul(nanoseconds_factor).ok_or(DurationError)?;
+    add(seconds, nanoseconds, out)?;
+    // the fractional part
+    if let Some((numerator, denominator)) = fraction {
+        let (seconds, nanoseconds) = if nanoseconds_factor == 1 {
+            return Err(DurationError);
+        } else if seconds_factor < 60 * 60 {
+            let factor = seconds_factor * NANOS_PER_SECOND + nanoseconds_factor;
+            (0, exact_div(numerator, factor, denominator)?)
+        } else {
+            (exact_div(numerator, seconds_factor, denominator)?, 0)
+        };
+        add(seconds, nanoseconds, out)?;
+    }
+    Ok(())
+}
+
+/// Computes `n * factor / denominator` and fails if the result is not an integer.
+fn exact_div(n: u64, factor: u64, denominator: u64) -> Result<u64, DurationError> {
+    let n = n.checked_mul(factor).ok_or(DurationError)?;
+    if n % denominator != 0 {
+        return Err(DurationError);
+    }
+    Ok(n / denominator)
+}
+
+fn add(seconds: u64, nanoseconds: u64, out: &mut StdDuration) -> Result<(), DurationError> {
+    let nanoseconds = (out.subsec_nanos() as u64)
+        .checked_add(nanoseconds)
+        .ok_or(DurationError)?;
+    let seconds = seconds
+        .checked_add(nanoseconds / NANOS_PER_SECOND)
+        .and_then(|s| s.checked_add(out.as_secs()))
+        .ok_or(DurationError)?;
+    *out = StdDuration::new(seconds, (nanoseconds % NANOS_PER_SECOND) as u32);
+    Ok(())
+}
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const SECONDS_PER_MONTH: u64 = 2_630_016;
+const SECONDS_PER_YEAR: u64 = 31_557_600;
+
+/// Unit names, seconds and nanoseconds per unit.
+const UNITS: [(&[&str], u64, u64); 10] = [
+    (&["nanos", "nsec", "ns"], 0, 1),
+    (&["usec", "us", "µs"], 0, 1000),
+    (&["millis", "msec", "ms"], 0, 1_000_000),
+    (&["seconds", "second", "secs", "sec", "s"], 1, 0),
+    (&["minutes", "minute", "min", "mins", "m"], 60, 0),
+    (&["hours", "hour", "hr", "hrs", "h"], 60 * 60, 0),
+    (&["days", "day", "d"], SECONDS_PER_DAY, 0),
+    (&["weeks", "week", "wk", "wks", "w"], 7 * SECONDS_PER_DAY, 0),
+    (&["months", "month", "M"], SECONDS_PER_MONTH, 0),
+    (&["years", "year", "yr", "yrs", "y"], SECONDS_PER_YEAR, 0),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0s", humantime_duration(0, 0).to_string());
+        assert_eq!("1ns", humantime_duration(0, 1).to_string());
+        assert_eq!("32ms", humantime_duration(0, 32_000_000).to_string());
+        assert_eq!(
+            "1s 1ms 1us 1ns",
+            humantime_duration(1, 1_001_001).to_string()
+        );
+        assert_eq!("2h 37m", humantime_duration(9420, 0).to_string());
+        assert_eq!("1day", humantime_duration(86400, 0).to_string());
+        assert_eq!("15days 2m 2s", humantime_duration(1_296_122, 0).to_string());
+        assert_eq!("1month", humantime_duration(2_630_016, 0).to_string());
+        assert_eq!("2years", humantime_duration(63_115_200, 0).to_string());
+        let string = humantime_duration(
+            584542046089 * SECONDS_PER_YEAR
+                + 11 * SECONDS_PER_MONTH
+                + 29 * SECONDS_PER_DAY
+                + 23 * 60 * 60
+                + 59 * 60
+                + 59,
+            999_999_999,
+        )
+        .to_string();
+        assert_eq!(
+            HumantimeDuration::MAX_STRING_LEN,
+            string.len(),
+            "string = `{}`",
+            string
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(humantime_duration(0, 0), "0".parse().unwrap());
+        assert_eq!(humantime_duration(9420, 0), "2h 37min".parse().unwrap());
+        assert_eq!(humantime_duration(0, 32_000_000), "32ms".parse().unwrap());
+        assert_eq!(humantime_duration(4, 200_000_000), "4.2s".parse().unwrap());
+        assert_eq!(
+            humantime_duration(1_296_122, 0),
+            "15days 2min 2s".parse().unwrap()
+        );
+        assert_eq!(humantime_duration(63_115_200, 0), "2years".parse().unwrap());
+        assert_eq!(humantime_duration(2_630_016, 0), "1M".parse().unwrap());
+        assert_eq!(humantime_duration(60, 0), "1m".parse().unwrap());
+        assert_eq!(humantime_duration(12, 0), "1 2 s".parse().unwrap());
+        assert_eq!(humantime_duration(61, 0), "1m1s".parse().unwrap());
+        assert_eq!(humantime_duration(0, 1000), "1µs".parse().unwrap());
+        assert_eq!(humantime_duration(30, 0), " 0.5 min ".parse().unwrap());
+        assert_eq!(humantime_duration(5400, 0), "1.5hours".parse().unwrap());
+        for s in [
+            "",
+            " ",
+            "00",
+            "1",
+            "s",
+            "1x",
+            "1S",
+            "1μs",
+            "1.s",
+            "1.5ns",
+            "1.0000000001s",
+            "-1s",
+            "1s,2s",
+            "18446744073709551616s",
+            "18446744073709551615s 1s",
+            "18446744073709551615ms",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<HumantimeDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_same_as_humantime() {
+        arbtest(|u| {
+            let mut string = String::new();
+            for _ in 0..u.int_in_range(0..=6)? {
+                string.push_str(u.choose(&TOKENS)?);
+            }
+            let expected = humantime::parse_duration(&string).ok();
+            let actual = string
+                .parse::<HumantimeDuration>()
+                .ok()
+                .map(|duration| duration.0 .0);
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_display_same_as_humantime() {
+        arbtest(|u| {
+            let duration: HumantimeDuration = u.arbitrary()?;
+            let expected = humantime::format_duration(duration.0 .0).to_string();
+            let actual = duration.to_string();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<HumantimeDuration>(
+            &[
+                "0", "1", "9", "10", "ns", "us", "µs", "μs", "ms", "s", "min", "m", "M", "hours",
+                "d", "y", "x", " ", "\t", "+", ".",
+            ],
+            20,
+            |s| s.contains('.') || s.chars().filter(char::is_ascii_digit).count() > 6,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: HumantimeDuration = u.arbitrary()?;
+            let string = expected.to_string();
+            let actual: HumantimeDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn humantime_duration(seconds: u64, nanoseconds: u32) -> HumantimeDuration {
+        HumantimeDuration(Duration(StdDuration::new(seconds, nanoseconds)))
+    }
+
+    const TOKENS: [&str; 39] = [
+        "0",
+        "1",
+        "7",
+        "25",
+        "1000000000",
+        "18446744073709551615",
+        ".",
+        ".5",
+        ".000000001",
+        " ",
+        "\t",
+        "\u{a0}",
+        "ns",
+        "nsec",
+        "us",
+        "µs",
+        "μs",
+        "ms",
+        "millis",
+        "s",
+        "sec",
+        "seconds",
+        "m",
+        "min",
+        "minutes",
+        "h",
+        "hr",
+        "hours",
+        "d",
+        "days",
+        "w",
+        "weeks",
+        "M",
+        "months",
+        "y",
+        "years",
+        "S",
+        "x",
+        "-",
+    ];
+}
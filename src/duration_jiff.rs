@@ -0,0 +1,68 @@
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationConversionError;
+
+impl TryFrom<Duration> for jiff::SignedDuration {
+    type Error = DurationConversionError;
+    fn try_from(other: Duration) -> Result<Self, Self::Error> {
+        Self::try_from(other.0).map_err(|_| DurationConversionError::OutOfRange)
+    }
+}
+
+impl TryFrom<jiff::SignedDuration> for Duration {
+    type Error = DurationConversionError;
+    fn try_from(other: jiff::SignedDuration) -> Result<Self, Self::Error> {
+        if other.is_negative() {
+            return Err(DurationConversionError::Negative);
+        }
+        StdDuration::try_from(other)
+            .map(Self)
+            .map_err(|_| DurationConversionError::OutOfRange)
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(DurationConversionError::Negative),
+            Duration::try_from(jiff::SignedDuration::from_nanos(-1))
+        );
+        assert_eq!(
+            Err(DurationConversionError::Negative),
+            Duration::try_from(jiff::SignedDuration::MIN)
+        );
+        assert_eq!(
+            Err(DurationConversionError::OutOfRange),
+            jiff::SignedDuration::try_from(Duration(StdDuration::MAX))
+        );
+        assert_eq!(
+            Ok(Duration(StdDuration::new(i64::MAX as u64, 999_999_999))),
+            Duration::try_from(jiff::SignedDuration::MAX)
+        );
+    }
+
+    #[test]
+    fn test_conversion_symmetry() {
+        arbtest(|u| {
+            let expected: Duration = u.arbitrary()?;
+            if let Ok(duration) = jiff::SignedDuration::try_from(expected) {
+                assert_eq!(Ok(expected), Duration::try_from(duration));
+            }
+            let expected = Duration(StdDuration::new(
+                u.int_in_range(0..=i64::MAX as u64)?,
+                u.int_in_range(0..=999_999_999)?,
+            ));
+            let duration = jiff::SignedDuration::try_from(expected).unwrap();
+            assert_eq!(Ok(expected), Duration::try_from(duration));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,364 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Exact duration in [nginx configuration](https://nginx.org/en/docs/syntax.html) format
+(`proxy_read_timeout 1h 30m;`).
+
+Parsing follows nginx's `ngx_parse_time`:
+units `w`, `d`, `h`, `m`, `s` and `ms` are case-sensitive
+and appear at most once each in this order,
+components may be separated by spaces (`1h 30m`) or not (`1h30m`),
+the trailing number without a unit is the number of seconds,
+and durations longer than `i64::MAX` milliseconds are rejected.
+
+Months (`M`) and years (`y`) are approximations (30 and 365 days respectively)
+and are rejected by [`FromStr`](core::str::FromStr);
+use [`NginxDuration::from_str_approximate`] to accept them.
+nginx itself rejects them in millisecond directives (`*_timeout`)
+and rejects `ms` in directives that are measured in seconds (`expires`, `ssl_session_timeout`).
+
+Formatting uses weeks, days, hours, minutes, seconds and milliseconds separated by spaces.
+nginx does not have units smaller than a millisecond,
+hence the sub-millisecond part is truncated when formatting.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct NginxDuration(pub Duration);
+
+impl NginxDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 36;
+
+    /**
+    Parses the duration allowing approximate units: months (`M`, 30 days) and years (`y`, 365 days).
+
+    Returns the duration and `true` if any approximate unit was used.
+    */
+    pub fn from_str_approximate(other: &str) -> Result<(Self, bool), DurationError> {
+        let mut step = Step::Start;
+        let mut valid = false;
+        let mut approximate = false;
+        let mut value: u64 = 0;
+        let mut total: u64 = 0;
+        let mut bytes = other.bytes().peekable();
+        while let Some(b) = bytes.next() {
+            if b.is_ascii_digit() {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add((b - b'0') as u64))
+                    .filter(|value| *value <= MAX_MILLISECONDS)
+                    .ok_or(DurationError)?;
+                valid = true;
+                continue;
+            }
+            let (next, factor) = match b {
+                b'y' => (Step::Year, 365 * MILLIS_PER_DAY),
+                b'M' => (Step::Month, 30 * MILLIS_PER_DAY),
+                b'w' => (Step::Week, 7 * MILLIS_PER_DAY),
+                b'd' => (Step::Day, MILLIS_PER_DAY),
+                b'h' => (Step::Hour, 60 * 60 * MILLIS_PER_SECOND),
+                b'm' if bytes.peek() == Some(&b's') => {
+                    bytes.next();
+                    (Step::Millisecond, 1)
+                }
+                b'm' => (Step::Minute, 60 * MILLIS_PER_SECOND),
+                b's' => (Step::Second, MILLIS_PER_SECOND),
+                // the number before the space is the number of seconds
+                b' ' if step < Step::Second => (Step::Last, MILLIS_PER_SECOND),
+                _ => return Err(DurationError),
+            };
+            if step >= next {
+                return Err(DurationError);
+            }
+            if matches!(next, Step::Year | Step::Month) {
+                approximate = true;
+            }
+            step = next;
+            total = value
+                .checked_mul(factor)
+                .and_then(|value| value.checked_add(total))
+                .filter(|total| *total <= MAX_MILLISECONDS)
+                .ok_or(DurationError)?;
+            value = 0;
+            while bytes.next_if_eq(&b' ').is_some() {}
+        }
+        if !valid {
+            return Err(DurationError);
+        }
+        let total = value
+            .checked_mul(MILLIS_PER_SECOND)
+            .and_then(|value| value.checked_add(total))
+            .filter(|total| *total <= MAX_MILLISECONDS)
+            .ok_or(DurationError)?;
+        Ok((Self(Duration(StdDuration::from_millis(total))), approximate))
+    }
+}
+
+impl Display for NginxDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let seconds = self.0 .0.as_secs();
+        let milliseconds = self.0 .0.subsec_millis();
+        if seconds == 0 && milliseconds == 0 {
+            return f.write_str("0s");
+        }
+        let components = [
+            (seconds / (7 * 24 * 60 * 60), "w"),
+            (seconds / (24 * 60 * 60) % 7, "d"),
+            (seconds / (60 * 60) % 24, "h"),
+            (seconds / 60 % 60, "m"),
+            (seconds % 60, "s"),
+            (milliseconds as u64, "ms"),
+        ];
+        let mut separator = "";
+        for (n, unit) in components {
+            if n != 0 {
+                write!(f, "{}{}{}", separator, n, unit)?;
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NginxDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        match Self::from_str_approximate(other)? {
+            (duration, false) => Ok(duration),
+            (_, true) => Err(DurationError),
+        }
+    }
+}
+
+impl From<Duration> for NginxDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<NginxDuration> for Duration {
+    fn from(other: NginxDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for NginxDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for NginxDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NginxDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ NginxDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for NginxDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "an nginx time")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for NginxDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "NginxDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::NginxDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Duration in nginx configuration format with units w, d, h, m, s and ms in this order; the number without a unit is the number of seconds",
+            r"^(?=.*[0-9])([0-9]*w *)?([0-9]*d *)?([0-9]*h *)?([0-9]*m(?!s) *)?(([0-9]*s *)?([0-9]*ms *)?[0-9]*|[0-9]* +[0-9]*)$",
+            &["30s", "1h 30m", "500ms"],
+        )
+    }
+}
+
+/// Units in the order they appear in nginx time strings.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Step {
+    Start,
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Last,
+}
+
+const MILLIS_PER_SECOND: u64 = 1000;
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * MILLIS_PER_SECOND;
+const MAX_MILLISECONDS: u64 = i64::MAX as u64;
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0s", nginx_millis(0).to_string());
+        assert_eq!("1ms", nginx_millis(1).to_string());
+        assert_eq!("1s 500ms", nginx_millis(1_500).to_string());
+        assert_eq!("10s", nginx_millis(10_000).to_string());
+        assert_eq!("1m", nginx_millis(60_000).to_string());
+        assert_eq!("1h 30m", nginx_millis(5_400_000).to_string());
+        assert_eq!("1d 1s", nginx_millis(86_401_000).to_string());
+        assert_eq!("2w 3d", nginx_millis(17 * 86_400_000).to_string());
+        assert_eq!(
+            "0s",
+            NginxDuration(Duration(StdDuration::from_micros(999))).to_string()
+        );
+        let string = NginxDuration(Duration(StdDuration::new(
+            30500568904942 * 7 * 24 * 60 * 60 + 7 * 24 * 60 * 60 - 1,
+            999_999_999,
+        )))
+        .to_string();
+        assert_eq!(
+            NginxDuration::MAX_STRING_LEN,
+            string.len(),
+            "string = `{}`",
+            string
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(nginx_millis(10_000), "10s".parse().unwrap());
+        assert_eq!(nginx_millis(10_000), "10".parse().unwrap());
+        assert_eq!(nginx_millis(500), "500ms".parse().unwrap());
+        assert_eq!(nginx_millis(5_400_000), "1h 30m".parse().unwrap());
+        assert_eq!(nginx_millis(5_400_000), "1h30m".parse().unwrap());
+        assert_eq!(nginx_millis(5_400_000), "1h  30m".parse().unwrap());
+        assert_eq!(nginx_millis(5_430_000), "1h 30m 30".parse().unwrap());
+        assert_eq!(nginx_millis(5_430_000), "1h 30m30".parse().unwrap());
+        assert_eq!(nginx_millis(5_430_000), "1h 30m 30s".parse().unwrap());
+        assert_eq!(nginx_millis(1_500), "1s 500ms".parse().unwrap());
+        assert_eq!(nginx_millis(3_000), "1s 2".parse().unwrap());
+        assert_eq!(nginx_millis(1_000), "1s ".parse().unwrap());
+        assert_eq!(nginx_millis(17 * 86_400_000), "2w 3d".parse().unwrap());
+        assert_eq!(nginx_millis(0), "0".parse().unwrap());
+        assert_eq!(nginx_millis(0), "0s".parse().unwrap());
+        assert_eq!(
+            nginx_millis(i64::MAX as u64),
+            "9223372036854775807ms".parse().unwrap()
+        );
+        for s in [
+            "",
+            " ",
+            "s",
+            "1M",
+            "1y",
+            "1S",
+            "1H",
+            "1sec",
+            "1min",
+            "1 s",
+            "1 500ms",
+            "1s 1h",
+            "1s 1s",
+            "1ms 1s",
+            "1ms 2 3",
+            "-1s",
+            "+1s",
+            "1.5s",
+            "9223372036854775808ms",
+            "9223372036854776",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<NginxDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_approximate() {
+        assert_eq!(
+            (nginx_millis(30 * 86_400_000), true),
+            NginxDuration::from_str_approximate("1M").unwrap()
+        );
+        assert_eq!(
+            (nginx_millis(365 * 86_400_000 + 3_600_000), true),
+            NginxDuration::from_str_approximate("1y 1h").unwrap()
+        );
+        assert_eq!(
+            (nginx_millis(3_600_000), false),
+            NginxDuration::from_str_approximate("1h").unwrap()
+        );
+        for s in ["1M 1y", "1y1y", "1w 1M"] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", NginxDuration::from_str_approximate(s)),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<NginxDuration>(
+            &[
+                "0", "1", "9", "10", "y", "M", "w", "d", "h", "m", "s", "ms", " ", "\t", "+", ".",
+            ],
+            9,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected = nginx_millis(u.int_in_range(0..=i64::MAX as u64)?);
+            let string = expected.to_string();
+            let actual: NginxDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn nginx_millis(milliseconds: u64) -> NginxDuration {
+        NginxDuration(Duration(StdDuration::from_millis(milliseconds)))
+    }
+}
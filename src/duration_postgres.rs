@@ -0,0 +1,234 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::parse_guc;
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Exact duration in [PostgreSQL configuration](https://www.postgresql.org/docs/current/config-setting.html#CONFIG-SETTING-NAMES-VALUES)
+format.
+
+Parsing follows PostgreSQL's `parse_int`:
+units `us`, `ms`, `s`, `min`, `h` and `d` are case-sensitive,
+whitespace is allowed around the number and the unit,
+integers may be written in octal (`010`) or hexadecimal (`0x10`) form,
+decimal fractions and exponents (`1.5s`, `1e3ms`) are rounded to the nearest microsecond,
+and the value without a unit is the number of milliseconds
+(the base unit of most PostgreSQL time settings, e.g. `statement_timeout`).
+
+Formatting produces the same output as PostgreSQL's `SHOW` command:
+the largest unit that divides the duration exactly, no space between the number and the unit,
+and zero without a unit.
+PostgreSQL does not have units smaller than a microsecond,
+hence the sub-microsecond part is truncated when formatting.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct PostgresDuration(pub Duration);
+
+impl PostgresDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 28;
+}
+
+impl Display for PostgresDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let micros = self.0 .0.as_micros();
+        if micros == 0 {
+            return f.write_str("0");
+        }
+        for (unit, factor) in UNITS {
+            if micros % factor == 0 {
+                return write!(f, "{}{}", micros / factor, unit);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PostgresDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let micros = parse_guc(other, &UNITS, MICROS_PER_MILLI).ok_or(DurationError)?;
+        let seconds: u64 = (micros / MICROS_PER_SECOND)
+            .try_into()
+            .map_err(|_| DurationError)?;
+        let nanoseconds = (micros % MICROS_PER_SECOND) as u32 * 1000;
+        Ok(Self(Duration(StdDuration::new(seconds, nanoseconds))))
+    }
+}
+
+impl From<Duration> for PostgresDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<PostgresDuration> for Duration {
+    fn from(other: PostgresDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for PostgresDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PostgresDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PostgresDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ PostgresDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for PostgresDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a PostgreSQL time setting")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for PostgresDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "PostgresDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::PostgresDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Duration in PostgreSQL configuration format with optional unit us, ms, s, min, h or d; the number without a unit is the number of milliseconds",
+            r"^[ \t\n\x0B\x0C\r]*[+-]?(0[xX][0-9a-fA-F]+|(0[0-7]*|[1-9][0-9]*)(\.[0-9]*)?([eE][+-]?[0-9]+)?|\.[0-9]+([eE][+-]?[0-9]+)?)([ \t\n\x0B\x0C\r]*(d|h|min|s|ms|us))?[ \t\n\x0B\x0C\r]*$",
+            &["30s", "5min", "100ms"],
+        )
+    }
+}
+
+const MICROS_PER_MILLI: u128 = 1000;
+const MICROS_PER_SECOND: u128 = 1000 * MICROS_PER_MILLI;
+
+const UNITS: [(&str, u128); 6] = [
+    ("d", 24 * 60 * 60 * MICROS_PER_SECOND),
+    ("h", 60 * 60 * MICROS_PER_SECOND),
+    ("min", 60 * MICROS_PER_SECOND),
+    ("s", MICROS_PER_SECOND),
+    ("ms", MICROS_PER_MILLI),
+    ("us", 1),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", postgres_micros(0).to_string());
+        assert_eq!("1us", postgres_micros(1).to_string());
+        assert_eq!("1500us", postgres_micros(1500).to_string());
+        assert_eq!("100ms", postgres_micros(100_000).to_string());
+        assert_eq!("1500ms", postgres_micros(1_500_000).to_string());
+        assert_eq!("5s", postgres_micros(5_000_000).to_string());
+        assert_eq!("1min", postgres_micros(60_000_000).to_string());
+        assert_eq!("90s", postgres_micros(90_000_000).to_string());
+        assert_eq!("1h", postgres_micros(3_600_000_000).to_string());
+        assert_eq!("1d", postgres_micros(86_400_000_000).to_string());
+        assert_eq!(
+            "0",
+            PostgresDuration(Duration(StdDuration::from_nanos(999))).to_string()
+        );
+        assert_eq!(
+            PostgresDuration::MAX_STRING_LEN,
+            PostgresDuration(Duration(StdDuration::new(u64::MAX, 999_999_999)))
+                .to_string()
+                .len()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(postgres_micros(1), "1us".parse().unwrap());
+        assert_eq!(postgres_micros(100_000), "100ms".parse().unwrap());
+        assert_eq!(postgres_micros(100_000), "100".parse().unwrap());
+        assert_eq!(postgres_micros(5_000_000), "5s".parse().unwrap());
+        assert_eq!(postgres_micros(5_000_000), " 5 s ".parse().unwrap());
+        assert_eq!(postgres_micros(60_000_000), "1min".parse().unwrap());
+        assert_eq!(postgres_micros(3_600_000_000), "1h".parse().unwrap());
+        assert_eq!(postgres_micros(86_400_000_000), "1d".parse().unwrap());
+        assert_eq!(postgres_micros(1_500_000), "1.5s".parse().unwrap());
+        assert_eq!(postgres_micros(1_000_000), "1e3ms".parse().unwrap());
+        assert_eq!(postgres_micros(2), "1.5us".parse().unwrap());
+        assert_eq!(postgres_micros(0), "0".parse().unwrap());
+        for s in [
+            "", "1S", "1m", "1M", "1Min", "1sec", "1 min x", "-1", "-1s", "1y", "1e",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<PostgresDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<PostgresDuration>(
+            &[
+                "0", "1", "9", "10", "7", "8", "0x", "a", "d", "e", "E", "h", "min", "s", "ms",
+                "us", "m", " ", "\t", "+", "-", ".",
+            ],
+            3,
+            |s| s.contains('-'),
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let duration: PostgresDuration = u.arbitrary()?;
+            let expected = postgres_micros(duration.0 .0.as_micros());
+            let string = duration.to_string();
+            let actual: PostgresDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn postgres_micros(micros: u128) -> PostgresDuration {
+        PostgresDuration(Duration(StdDuration::new(
+            (micros / MICROS_PER_SECOND) as u64,
+            (micros % MICROS_PER_SECOND) as u32 * 1000,
+        )))
+    }
+}
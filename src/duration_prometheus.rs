@@ -0,0 +1,320 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Exact duration in [Prometheus](https://prometheus.io/docs/prometheus/latest/configuration/configuration/#duration)
+format.
+
+Parsing follows Prometheus' `model.ParseDuration`:
+units `y` (365 days), `w`, `d`, `h`, `m`, `s` and `ms` must appear at most once
+in descending order (`1y2w3d4h5m6s7ms`), fractions, signs and whitespace are not allowed,
+`0` is the only value without a unit,
+and durations longer than `i64::MAX` nanoseconds are rejected.
+
+Formatting produces the same output as Prometheus' `model.Duration.String`:
+years and weeks are only used when they divide the duration exactly (`90d` rather than `12w6d`)
+and the sub-millisecond part is truncated.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct PrometheusDuration(pub Duration);
+
+impl PrometheusDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 30;
+}
+
+impl Display for PrometheusDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut ms = self.0 .0.as_millis();
+        if ms == 0 {
+            return f.write_str("0s");
+        }
+        for (unit, factor, exact) in FORMAT_UNITS {
+            let factor = factor as u128;
+            if exact && ms % factor != 0 {
+                continue;
+            }
+            let n = ms / factor;
+            if n != 0 {
+                write!(f, "{}{}", n, unit)?;
+                ms -= n * factor;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PrometheusDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        if other == "0" {
+            return Ok(Self::default());
+        }
+        if other.is_empty() {
+            return Err(DurationError);
+        }
+        let mut ms: u64 = 0;
+        let mut rest = other;
+        let mut units = &PARSE_UNITS[..];
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|ch: char| !ch.is_ascii_digit())
+                .ok_or(DurationError)?;
+            let number: u64 = rest[..number_len].parse().map_err(|_| DurationError)?;
+            rest = &rest[number_len..];
+            // units that are left must follow in descending order
+            let i = units
+                .iter()
+                .position(|(unit, _)| {
+                    rest.starts_with(unit) && !(*unit == "m" && rest.starts_with("ms"))
+                })
+                .ok_or(DurationError)?;
+            let (unit, factor) = units[i];
+            rest = &rest[unit.len()..];
+            units = &units[(i + 1)..];
+            ms = number
+                .checked_mul(factor)
+                .and_then(|x| x.checked_add(ms))
+                .ok_or(DurationError)?;
+        }
+        if ms > MAX_MILLISECONDS {
+            return Err(DurationError);
+        }
+        Ok(Self(Duration(StdDuration::from_millis(ms))))
+    }
+}
+
+impl From<Duration> for PrometheusDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<PrometheusDuration> for Duration {
+    fn from(other: PrometheusDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for PrometheusDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PrometheusDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PrometheusDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ PrometheusDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for PrometheusDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a Prometheus duration")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for PrometheusDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "PrometheusDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::PrometheusDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut schema = crate::string_schema(
+            "Duration in Prometheus format with units y, w, d, h, m, s and ms in this order",
+            r"^(0|([0-9]+y)?([0-9]+w)?([0-9]+d)?([0-9]+h)?([0-9]+m)?([0-9]+s)?([0-9]+ms)?)$",
+            &["1h30m", "5m", "90d"],
+        );
+        // the pattern matches the empty string
+        schema.insert("minLength".into(), 1.into());
+        schema
+    }
+}
+
+const MS_PER_SECOND: u64 = 1000;
+const MS_PER_MINUTE: u64 = 60 * MS_PER_SECOND;
+const MS_PER_HOUR: u64 = 60 * MS_PER_MINUTE;
+const MS_PER_DAY: u64 = 24 * MS_PER_HOUR;
+const MS_PER_WEEK: u64 = 7 * MS_PER_DAY;
+const MS_PER_YEAR: u64 = 365 * MS_PER_DAY;
+
+// Durations are stored as nanoseconds in 64-bit signed integer.
+const MAX_MILLISECONDS: u64 = i64::MAX as u64 / 1_000_000;
+
+const PARSE_UNITS: [(&str, u64); 7] = [
+    ("y", MS_PER_YEAR),
+    ("w", MS_PER_WEEK),
+    ("d", MS_PER_DAY),
+    ("h", MS_PER_HOUR),
+    ("m", MS_PER_MINUTE),
+    ("s", MS_PER_SECOND),
+    ("ms", 1),
+];
+
+// Unit, no. of milliseconds and whether the unit is only used when it divides the duration.
+const FORMAT_UNITS: [(&str, u64, bool); 7] = [
+    ("y", MS_PER_YEAR, true),
+    ("w", MS_PER_WEEK, true),
+    ("d", MS_PER_DAY, false),
+    ("h", MS_PER_HOUR, false),
+    ("m", MS_PER_MINUTE, false),
+    ("s", MS_PER_SECOND, false),
+    ("ms", 1, false),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0s", prometheus_ms(0).to_string());
+        assert_eq!("1m30s", prometheus_ms(90 * MS_PER_SECOND).to_string());
+        assert_eq!("15m", prometheus_ms(15 * MS_PER_MINUTE).to_string());
+        assert_eq!("90d", prometheus_ms(90 * MS_PER_DAY).to_string());
+        assert_eq!("2w", prometheus_ms(14 * MS_PER_DAY).to_string());
+        assert_eq!("1y", prometheus_ms(MS_PER_YEAR).to_string());
+        assert_eq!(
+            "382d4h5m6s7ms",
+            "1y2w3d4h5m6s7ms"
+                .parse::<PrometheusDuration>()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "0s",
+            PrometheusDuration(Duration(StdDuration::from_nanos(999_999))).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(prometheus_ms(0), "0".parse().unwrap());
+        assert_eq!(prometheus_ms(0), "0s".parse().unwrap());
+        assert_eq!(prometheus_ms(90 * MS_PER_SECOND), "90s".parse().unwrap());
+        assert_eq!(prometheus_ms(15 * MS_PER_MINUTE), "15m".parse().unwrap());
+        assert_eq!(prometheus_ms(7), "7ms".parse().unwrap());
+        assert_eq!(prometheus_ms(MS_PER_MINUTE + 7), "1m7ms".parse().unwrap());
+        assert_eq!(
+            prometheus_ms(
+                MS_PER_YEAR
+                    + 2 * MS_PER_WEEK
+                    + 3 * MS_PER_DAY
+                    + 4 * MS_PER_HOUR
+                    + 5 * MS_PER_MINUTE
+                    + 6 * MS_PER_SECOND
+                    + 7
+            ),
+            "1y2w3d4h5m6s7ms".parse().unwrap()
+        );
+        assert_eq!(
+            prometheus_ms(MAX_MILLISECONDS),
+            "9223372036854ms".parse().unwrap()
+        );
+        for s in [
+            "",
+            "1",
+            "s",
+            "1.5s",
+            "-1s",
+            "+1s",
+            "1s1m",
+            "1m1m",
+            "1 s",
+            " 1s",
+            "1s ",
+            "1S",
+            "1sms",
+            "1mss",
+            "1ms1s",
+            "1M",
+            "1x",
+            "9223372036855ms",
+            "293y",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<PrometheusDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_string_len() {
+        let string = PrometheusDuration(Duration(StdDuration::new(
+            213503982334600 * 24 * 60 * 60 + 24 * 60 * 60 - 1,
+            999_999_999,
+        )))
+        .to_string();
+        assert_eq!(
+            PrometheusDuration::MAX_STRING_LEN,
+            string.len(),
+            "string = `{}`",
+            string
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<PrometheusDuration>(
+            &[
+                "0", "1", "9", "10", "y", "w", "d", "h", "m", "s", "ms", " ", "+", ".",
+            ],
+            2,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected = prometheus_ms(u.int_in_range(0..=MAX_MILLISECONDS)?);
+            let string = expected.to_string();
+            let actual: PrometheusDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn prometheus_ms(ms: u64) -> PrometheusDuration {
+        PrometheusDuration(Duration(StdDuration::from_millis(ms)))
+    }
+}
@@ -0,0 +1,46 @@
+use schemars::json_schema;
+
+use crate::Duration;
+
+impl schemars::JsonSchema for Duration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "Duration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::Duration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        json_schema!({
+            "description": "Duration with optional unit ns, μs, ms, s, m, h or d or the number of seconds",
+            "type": ["string", "integer"],
+            "pattern": PATTERN,
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "examples": ["30s", "5m", "1d", 60],
+        })
+    }
+}
+
+/// Matches exactly the strings that `Duration::from_str` accepts.
+const PATTERN: &str = r"^\s*\+?[0-9]+\s*(ns|μs|ms|s|m|h|d)?\s*$";
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use super::*;
+    use crate::str_schema::tests::check_schema;
+
+    #[test]
+    fn test_schema() {
+        check_schema::<Duration>(
+            &[
+                "0", "1", "9", "10", "ns", "μs", "µs", "us", "ms", "s", "m", "h", "d", "M", "x",
+                " ", "\t", "\n", "+", "-", ".",
+            ],
+            14,
+            |_| false,
+        );
+    }
+}
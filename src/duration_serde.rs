@@ -1,14 +1,28 @@
 use core::fmt::Formatter;
 use core::fmt::Write;
+use core::time::Duration as StdDuration;
+
+use serde::de::Unexpected;
 
 use crate::Buffer;
+use crate::CheckedVisitor;
 use crate::Duration;
+use crate::ExpectedUnits;
+use crate::DURATION_UNITS;
 
 impl serde::Serialize for Duration {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        if !s.is_human_readable() {
+            // the same encoding as `std::time::Duration` uses
+            use serde::ser::SerializeStruct;
+            let mut state = s.serialize_struct("Duration", 2)?;
+            state.serialize_field("secs", &self.0.as_secs())?;
+            state.serialize_field("nanos", &self.0.subsec_nanos())?;
+            return state.end();
+        }
         let mut buf = Buffer::<{ Duration::MAX_STRING_LEN }>::new();
         let _ = write!(&mut buf, "{}", self);
         s.serialize_str(unsafe { core::str::from_utf8_unchecked(buf.as_slice()) })
@@ -20,7 +34,31 @@ impl<'a> serde::Deserialize<'a> for Duration {
     where
         D: serde::Deserializer<'a>,
     {
-        d.deserialize_str(DurationVisitor)
+        if d.is_human_readable() {
+            d.deserialize_any(DurationVisitor)
+        } else {
+            d.deserialize_struct("Duration", FIELDS, DurationVisitor)
+        }
+    }
+}
+
+/// Deserializes the duration the same way as [`Duration`] does and checks it with `check`.
+pub(crate) fn deserialize_duration_checked<'a, D, T, E>(
+    d: D,
+    check: impl FnOnce(Duration) -> Result<T, E>,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'a>,
+    E: core::fmt::Display,
+{
+    let visitor = CheckedVisitor {
+        inner: DurationVisitor,
+        check,
+    };
+    if d.is_human_readable() {
+        d.deserialize_any(visitor)
+    } else {
+        d.deserialize_struct("Duration", FIELDS, visitor)
     }
 }
 
@@ -30,17 +68,148 @@ impl<'a> serde::de::Visitor<'a> for DurationVisitor {
     type Value = Duration;
 
     fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
-        write!(formatter, "a string obtained by `Duration::to_string`")
+        write!(
+            formatter,
+            "a string obtained by `Duration::to_string`, the number of seconds or `{{secs, nanos}}` map"
+        )
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        value.parse().map_err(|_| E::custom("invalid duration"))
+        Duration::parse(value).map_err(|reason| {
+            E::invalid_value(
+                Unexpected::Str(value),
+                &ExpectedUnits {
+                    what: "a duration",
+                    units: DURATION_UNITS,
+                    reason,
+                },
+            )
+        })
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Duration(StdDuration::from_secs(value)))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        u64::try_from(value)
+            .map(|secs| Duration(StdDuration::from_secs(secs)))
+            .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'a>,
+    {
+        use serde::de::Error;
+        let secs = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let nanos = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        new_duration(secs, nanos)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'a>,
+    {
+        use serde::de::Error;
+        let mut secs: Option<u64> = None;
+        let mut nanos: Option<u32> = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Secs => {
+                    if secs.is_some() {
+                        return Err(A::Error::duplicate_field("secs"));
+                    }
+                    secs = Some(map.next_value()?);
+                }
+                Field::Nanos => {
+                    if nanos.is_some() {
+                        return Err(A::Error::duplicate_field("nanos"));
+                    }
+                    nanos = Some(map.next_value()?);
+                }
+            }
+        }
+        let secs = secs.ok_or_else(|| A::Error::missing_field("secs"))?;
+        let nanos = nanos.ok_or_else(|| A::Error::missing_field("nanos"))?;
+        new_duration(secs, nanos)
     }
 }
 
+/// Creates the duration the same way as `Deserialize` implementation of `std::time::Duration`.
+fn new_duration<E>(secs: u64, nanos: u32) -> Result<Duration, E>
+where
+    E: serde::de::Error,
+{
+    match secs.checked_add((nanos / NANOS_PER_SEC) as u64) {
+        Some(..) => Ok(Duration(StdDuration::new(secs, nanos))),
+        None => Err(E::custom("overflow deserializing Duration")),
+    }
+}
+
+/// `std::time::Duration` field.
+enum Field {
+    Secs,
+    Nanos,
+}
+
+impl<'a> serde::Deserialize<'a> for Field {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        d.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl<'a> serde::de::Visitor<'a> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("`secs` or `nanos`")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match value {
+            "secs" => Ok(Field::Secs),
+            "nanos" => Ok(Field::Nanos),
+            _ => Err(E::unknown_field(value, FIELDS)),
+        }
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match value {
+            b"secs" => Ok(Field::Secs),
+            b"nanos" => Ok(Field::Nanos),
+            _ => Err(E::custom("unknown field")),
+        }
+    }
+}
+
+const FIELDS: &[&str] = &["secs", "nanos"];
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
 #[cfg(all(test, not(feature = "no_std")))]
 mod tests {
 
@@ -68,6 +237,84 @@ mod tests {
             Duration(StdDuration::from_secs(10)),
             serde_yaml::from_str("10").unwrap()
         );
+        assert_eq!(
+            Duration(StdDuration::from_secs(30)),
+            serde_json::from_str("30").unwrap()
+        );
+        assert_eq!(
+            DurationWrapper {
+                duration: Duration(StdDuration::from_secs(30))
+            },
+            toml::from_str("duration = 30").unwrap()
+        );
+        assert_eq!(
+            Duration(StdDuration::new(1, 500_000_000)),
+            serde_json::from_str(r#"{"secs":1,"nanos":500000000}"#).unwrap()
+        );
+        assert_eq!(
+            Duration(StdDuration::new(3, 1)),
+            serde_json::from_str(r#"{"nanos":2000000001,"secs":1}"#).unwrap()
+        );
+        assert_eq!(
+            Duration(StdDuration::new(1, 2)),
+            serde_json::from_str("[1,2]").unwrap()
+        );
+        for s in [
+            "-1",
+            "1.5",
+            "null",
+            "[]",
+            "[1]",
+            r#"{"secs":1}"#,
+            r#"{"nanos":1}"#,
+            r#"{"secs":1,"nanos":1,"secs":1}"#,
+            r#"{"secs":1,"nanos":1,"millis":1}"#,
+            r#"{"secs":18446744073709551615,"nanos":1000000000}"#,
+        ] {
+            assert!(serde_json::from_str::<Duration>(s).is_err(), "s = `{}`", s);
+        }
+        let std_duration = StdDuration::new(12, 345);
+        assert_eq!(
+            Duration(std_duration),
+            serde_json::from_str(&serde_json::to_string(&std_duration).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serde_errors() {
+        for (s, error) in [
+            (
+                "\"10x\"",
+                "invalid value: string \"10x\", expected a duration with optional unit ns, μs, ms, s, m, h or d (unknown unit) at line 1 column 5",
+            ),
+            (
+                "\"1.5s\"",
+                "invalid value: string \"1.5s\", expected a duration with optional unit ns, μs, ms, s, m, h or d (not a non-negative integer) at line 1 column 6",
+            ),
+            (
+                "\"s\"",
+                "invalid value: string \"s\", expected a duration with optional unit ns, μs, ms, s, m, h or d (no number) at line 1 column 3",
+            ),
+            (
+                "\"18446744073709551616s\"",
+                "invalid value: string \"18446744073709551616s\", expected a duration with optional unit ns, μs, ms, s, m, h or d (the value is too large) at line 1 column 23",
+            ),
+            (
+                "-1",
+                "invalid value: integer `-1`, expected a string obtained by `Duration::to_string`, the number of seconds or `{secs, nanos}` map at line 1 column 2",
+            ),
+        ] {
+            assert_eq!(
+                error,
+                serde_json::from_str::<Duration>(s).unwrap_err().to_string(),
+                "s = `{}`",
+                s
+            );
+        }
+        assert_eq!(
+            "invalid value: string \"5 minutes\", expected a duration with optional unit ns, μs, ms, s, m, h or d (unknown unit)",
+            serde_yaml::from_str::<Duration>("5 minutes").unwrap_err().to_string()
+        );
     }
 
     #[test]
@@ -114,6 +361,30 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_serde_bincode() {
+        arbtest(|u| {
+            let expected: DurationWrapper = u.arbitrary()?;
+            let bytes = bincode::serialize(&expected).unwrap();
+            assert_eq!(bincode::serialize(&expected.duration.0).unwrap(), bytes);
+            let actual = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_serde_postcard() {
+        arbtest(|u| {
+            let expected: DurationWrapper = u.arbitrary()?;
+            let bytes = postcard::to_allocvec(&expected).unwrap();
+            assert_eq!(postcard::to_allocvec(&expected.duration.0).unwrap(), bytes);
+            let actual = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
     #[derive(
         serde::Serialize, serde::Deserialize, arbitrary::Arbitrary, Debug, PartialEq, Eq, Clone,
     )]
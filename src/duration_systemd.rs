@@ -0,0 +1,425 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::Duration;
+use crate::DurationError;
+
+/**
+Exact duration in [systemd.time(7)](https://www.freedesktop.org/software/systemd/man/latest/systemd.time.html)
+time span format.
+
+Parsing follows systemd's `parse_sec`: the components such as `5min 20s`, `1h30min` or `1.5s`
+are summed up, the default unit is second and `infinity` denotes an infinite time span.
+Formatting produces the same output as `systemd-analyze timespan`.
+
+systemd stores time spans as the number of microseconds in 64-bit unsigned integer,
+hence the sub-microsecond part is truncated when formatting,
+and durations that do not fit into this integer are formatted as `infinity`.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct SystemdDuration(pub Duration);
+
+impl SystemdDuration {
+    /// Max. length of the duration in string form.
+    pub const MAX_STRING_LEN: usize = 42;
+
+    /// Infinite time span.
+    pub const INFINITY: Self = Self(Duration(StdDuration::from_micros(USEC_INFINITY)));
+
+    /// Returns `true` if the duration is treated as infinite by systemd.
+    pub fn is_infinite(&self) -> bool {
+        self.0 .0.as_micros() >= USEC_INFINITY as u128
+    }
+}
+
+impl Display for SystemdDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.is_infinite() {
+            return f.write_str("infinity");
+        }
+        let mut t = self.0 .0.as_micros() as u64;
+        if t == 0 {
+            return f.write_str("0");
+        }
+        let mut separator = "";
+        for (unit, usec) in FORMAT_UNITS {
+            if t == 0 {
+                break;
+            }
+            if t < usec {
+                continue;
+            }
+            let a = t / usec;
+            let b = t % usec;
+            if t < USEC_PER_MINUTE && b != 0 {
+                // use dot notation
+                let mut num_digits = 0;
+                let mut x = usec;
+                while x > 1 {
+                    x /= 10;
+                    num_digits += 1;
+                }
+                write!(
+                    f,
+                    "{}{}.{:0width$}{}",
+                    separator,
+                    a,
+                    b,
+                    unit,
+                    width = num_digits
+                )?;
+                t = 0;
+            } else {
+                write!(f, "{}{}{}", separator, a, unit)?;
+                t = b;
+            }
+            separator = " ";
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SystemdDuration {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let other = other.trim_matches(is_whitespace);
+        if other == "infinity" {
+            return Ok(Self::INFINITY);
+        }
+        if other.is_empty() {
+            return Err(DurationError);
+        }
+        let mut total: u64 = 0;
+        let mut rest = other;
+        while !rest.is_empty() {
+            let integer_len = rest
+                .find(|ch: char| !ch.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let integer = &rest[..integer_len];
+            rest = &rest[integer_len..];
+            let fraction = match rest.strip_prefix('.') {
+                Some(s) => {
+                    let fraction_len = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+                    if fraction_len == 0 {
+                        return Err(DurationError);
+                    }
+                    rest = &s[fraction_len..];
+                    &s[..fraction_len]
+                }
+                None if integer.is_empty() => return Err(DurationError),
+                None => "",
+            };
+            let integer: u64 = if integer.is_empty() {
+                0
+            } else {
+                integer.parse().map_err(|_| DurationError)?
+            };
+            if integer > i64::MAX as u64 {
+                return Err(DurationError);
+            }
+            let after_number = rest;
+            rest = rest.trim_start_matches(is_whitespace);
+            let (unit_len, factor) = PARSE_UNITS
+                .iter()
+                .find(|(unit, _)| rest.starts_with(unit))
+                .map(|(unit, factor)| (unit.len(), *factor))
+                .unwrap_or((0, USEC_PER_SEC));
+            if unit_len == 0 && rest.len() == after_number.len() && !rest.is_empty() {
+                return Err(DurationError);
+            }
+            rest = rest[unit_len..].trim_start_matches(is_whitespace);
+            if integer >= USEC_INFINITY / factor {
+                return Err(DurationError);
+            }
+            total = add(total, integer * factor)?;
+            let mut m = factor / 10;
+            for digit in fraction.bytes() {
+                total = add(total, (digit - b'0') as u64 * m)?;
+                m /= 10;
+            }
+        }
+        Ok(Self(Duration(StdDuration::from_micros(total))))
+    }
+}
+
+impl From<Duration> for SystemdDuration {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<SystemdDuration> for Duration {
+    fn from(other: SystemdDuration) -> Self {
+        other.0
+    }
+}
+
+impl Deref for SystemdDuration {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SystemdDuration {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SystemdDuration {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ SystemdDuration::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for SystemdDuration {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a systemd time span")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for SystemdDuration {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "SystemdDuration".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::SystemdDuration".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Duration in systemd time span format; the components are summed up and the number without a unit is the number of seconds",
+            r"^[ \t\n\r]*(infinity|(([0-9]+(\.[0-9]+)?|\.[0-9]+)([ \t\n\r]*(seconds|second|sec|s|minutes|minute|min|months|month|M|msec|ms|m|hours|hour|hr|h|days|day|d|weeks|week|w|years|year|y|usec|us|μs|µs)|[ \t\n\r])[ \t\n\r]*)*([0-9]+(\.[0-9]+)?|\.[0-9]+)([ \t\n\r]*(seconds|second|sec|s|minutes|minute|min|months|month|M|msec|ms|m|hours|hour|hr|h|days|day|d|weeks|week|w|years|year|y|usec|us|μs|µs))?)[ \t\n\r]*$",
+            &["5min 20s", "1h30min", "infinity"],
+        )
+    }
+}
+
+const fn is_whitespace(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\n' | '\r')
+}
+
+fn add(total: u64, x: u64) -> Result<u64, DurationError> {
+    if x >= USEC_INFINITY - total {
+        return Err(DurationError);
+    }
+    Ok(total + x)
+}
+
+const USEC_INFINITY: u64 = u64::MAX;
+const USEC_PER_MSEC: u64 = 1000;
+const USEC_PER_SEC: u64 = 1000 * USEC_PER_MSEC;
+const USEC_PER_MINUTE: u64 = 60 * USEC_PER_SEC;
+const USEC_PER_HOUR: u64 = 60 * USEC_PER_MINUTE;
+const USEC_PER_DAY: u64 = 24 * USEC_PER_HOUR;
+const USEC_PER_WEEK: u64 = 7 * USEC_PER_DAY;
+const USEC_PER_MONTH: u64 = 2629800 * USEC_PER_SEC;
+const USEC_PER_YEAR: u64 = 31557600 * USEC_PER_SEC;
+
+// The order matters: the first unit that is a prefix of the input wins.
+const PARSE_UNITS: [(&str, u64); 30] = [
+    ("seconds", USEC_PER_SEC),
+    ("second", USEC_PER_SEC),
+    ("sec", USEC_PER_SEC),
+    ("s", USEC_PER_SEC),
+    ("minutes", USEC_PER_MINUTE),
+    ("minute", USEC_PER_MINUTE),
+    ("min", USEC_PER_MINUTE),
+    ("months", USEC_PER_MONTH),
+    ("month", USEC_PER_MONTH),
+    ("M", USEC_PER_MONTH),
+    ("msec", USEC_PER_MSEC),
+    ("ms", USEC_PER_MSEC),
+    ("m", USEC_PER_MINUTE),
+    ("hours", USEC_PER_HOUR),
+    ("hour", USEC_PER_HOUR),
+    ("hr", USEC_PER_HOUR),
+    ("h", USEC_PER_HOUR),
+    ("days", USEC_PER_DAY),
+    ("day", USEC_PER_DAY),
+    ("d", USEC_PER_DAY),
+    ("weeks", USEC_PER_WEEK),
+    ("week", USEC_PER_WEEK),
+    ("w", USEC_PER_WEEK),
+    ("years", USEC_PER_YEAR),
+    ("year", USEC_PER_YEAR),
+    ("y", USEC_PER_YEAR),
+    ("usec", 1),
+    ("us", 1),
+    ("μs", 1),
+    ("µs", 1),
+];
+
+const FORMAT_UNITS: [(&str, u64); 9] = [
+    ("y", USEC_PER_YEAR),
+    ("month", USEC_PER_MONTH),
+    ("w", USEC_PER_WEEK),
+    ("d", USEC_PER_DAY),
+    ("h", USEC_PER_HOUR),
+    ("min", USEC_PER_MINUTE),
+    ("s", USEC_PER_SEC),
+    ("ms", USEC_PER_MSEC),
+    ("us", 1),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", systemd_micros(0).to_string());
+        assert_eq!("5min 20s", systemd_micros(320000000).to_string());
+        assert_eq!("1h 30min", systemd_micros(5400000000).to_string());
+        assert_eq!("2w", systemd_micros(1209600000000).to_string());
+        assert_eq!("500ms", systemd_micros(500000).to_string());
+        assert_eq!("1.500ms", systemd_micros(1500).to_string());
+        assert_eq!("1min 1.500000s", systemd_micros(61500000).to_string());
+        assert_eq!("1y", systemd_micros(31557600000000).to_string());
+        assert_eq!(
+            "11month 4w 2d 4h 30min",
+            systemd_micros(31536000000000).to_string()
+        );
+        assert_eq!(
+            "31y 8month 1w 19h 46min 40s",
+            systemd_micros(1000000000000000).to_string()
+        );
+        assert_eq!("infinity", SystemdDuration::INFINITY.to_string());
+        assert_eq!(
+            "infinity",
+            SystemdDuration(Duration(StdDuration::MAX)).to_string()
+        );
+        assert_eq!("1us", systemd_micros(1).to_string());
+        assert_eq!(
+            "0",
+            SystemdDuration(Duration(StdDuration::from_nanos(999))).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(systemd_micros(320000000), "5min 20s".parse().unwrap());
+        assert_eq!(systemd_micros(5400000000), "1h30min".parse().unwrap());
+        assert_eq!(systemd_micros(1209600000000), "2 weeks".parse().unwrap());
+        assert_eq!(systemd_micros(500000), "500msec".parse().unwrap());
+        assert_eq!(SystemdDuration::INFINITY, " infinity ".parse().unwrap());
+        assert_eq!(systemd_micros(3000000), "1 2".parse().unwrap());
+        assert_eq!(systemd_micros(500000), ".5s".parse().unwrap());
+        assert_eq!(systemd_micros(12900000), "12.34s.56".parse().unwrap());
+        assert_eq!(systemd_micros(61500000), "1min 1.500000s".parse().unwrap());
+        assert_eq!(systemd_micros(1), "1μs".parse().unwrap());
+        assert_eq!(systemd_micros(1), "1µs".parse().unwrap());
+        assert_eq!(systemd_micros(2629800000000), "1M".parse().unwrap());
+        assert_eq!(systemd_micros(7200000000), "2 hr".parse().unwrap());
+        assert_eq!(systemd_micros(0), "0".parse().unwrap());
+        for s in [
+            "",
+            "5 x",
+            "5x",
+            "5mins",
+            "12.34.56",
+            "3.sec",
+            ".",
+            "-1s",
+            "infinity 1s",
+            "18446744073709551614us",
+            "9223372036854775808us",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<SystemdDuration>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_string_len() {
+        let string = systemd_micros(
+            584541 * USEC_PER_YEAR
+                + 11 * USEC_PER_MONTH
+                + 4 * USEC_PER_WEEK
+                + 2 * USEC_PER_DAY
+                + 10 * USEC_PER_HOUR
+                + 29 * USEC_PER_MINUTE
+                + 59 * USEC_PER_SEC
+                + 999999,
+        )
+        .to_string();
+        assert_eq!(
+            SystemdDuration::MAX_STRING_LEN,
+            string.len(),
+            "string = `{}`",
+            string
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<SystemdDuration>(
+            &[
+                "0", "1", "9", "10", "s", "sec", "min", "m", "M", "ms", "h", "d", "weeks", "y",
+                "us", "µs", "infinity", "x", " ", "\t", "+", ".",
+            ],
+            4,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let duration: SystemdDuration = u.arbitrary()?;
+            let expected = if duration.is_infinite() {
+                SystemdDuration::INFINITY
+            } else {
+                systemd_micros(duration.0 .0.as_micros() as u64)
+            };
+            let string = duration.to_string();
+            assert!(string.len() <= SystemdDuration::MAX_STRING_LEN);
+            let actual: SystemdDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn display_parse_symmetry_finite() {
+        arbtest(|u| {
+            let expected = systemd_micros(u.int_in_range(0..=USEC_INFINITY - 1)?);
+            let string = expected.to_string();
+            let actual: SystemdDuration = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn systemd_micros(micros: u64) -> SystemdDuration {
+        SystemdDuration(Duration(StdDuration::from_micros(micros)))
+    }
+}
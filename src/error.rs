@@ -0,0 +1,63 @@
+use core::fmt::Display;
+
+/// The reason why a string could not be parsed.
+///
+/// [`SizeError`](crate::SizeError) and [`DurationError`](crate::DurationError)
+/// do not carry the reason to stay zero-sized;
+/// the reason is used to produce informative serde errors
+/// and is a part of the bounded value errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The string does not contain a number.
+    NoNumber,
+    /// The number is not a non-negative integer.
+    InvalidNumber,
+    /// The unit is not supported.
+    UnknownUnit,
+    /// The value is too large.
+    Overflow,
+}
+
+impl ErrorKind {
+    /// Parses decimal digits distinguishing between overflow and invalid characters.
+    pub(crate) fn parse_integer<T: core::str::FromStr>(s: &str) -> Result<T, Self> {
+        s.parse().map_err(|_| {
+            if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+                Self::Overflow
+            } else {
+                Self::InvalidNumber
+            }
+        })
+    }
+}
+
+/// Expected value with the list of supported units and the reason why the actual value was rejected.
+#[cfg(any(feature = "serde", feature = "clap"))]
+pub(crate) struct ExpectedUnits {
+    pub(crate) what: &'static str,
+    pub(crate) units: &'static str,
+    pub(crate) reason: ErrorKind,
+}
+
+#[cfg(any(feature = "serde", feature = "clap"))]
+impl Display for ExpectedUnits {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{} with optional unit {} ({})",
+            self.what, self.units, self.reason
+        )
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let s = match self {
+            Self::NoNumber => "no number",
+            Self::InvalidNumber => "not a non-negative integer",
+            Self::UnknownUnit => "unknown unit",
+            Self::Overflow => "the value is too large",
+        };
+        f.write_str(s)
+    }
+}
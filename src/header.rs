@@ -0,0 +1,485 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use crate::parse_decimal;
+use crate::Duration;
+use crate::DurationError;
+use crate::Size;
+use crate::SizeError;
+
+/**
+`max-age` directive of [`Cache-Control`](https://www.rfc-editor.org/rfc/rfc9111#name-cache-control) HTTP header.
+
+Formatting produces `max-age=<seconds>`.
+The duration is rounded down to the whole number of seconds
+so that the response is never considered fresh for longer than the configured duration.
+Durations longer than [`MaxAge::MAX_SECONDS`] are replaced with this value.
+
+Parsing accepts the whole header value (`public, max-age=3600`),
+uses the first `max-age` directive, and fails if there is none.
+The directive name is case-insensitive and the value is either a token or a quoted string.
+As [required by RFC 9111](https://www.rfc-editor.org/rfc/rfc9111#name-delta-seconds),
+values greater than [`MaxAge::MAX_SECONDS`] (including the ones that overflow `u64`)
+are replaced with this value.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct MaxAge(pub Duration);
+
+impl MaxAge {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 18;
+
+    /// The greatest delta-seconds value that HTTP caches are required to represent (2<sup>31</sup>).
+    pub const MAX_SECONDS: u64 = 1 << 31;
+
+    /// Returns the number of seconds that is sent in the header.
+    pub const fn as_delta_seconds(&self) -> u64 {
+        let seconds = self.0 .0.as_secs();
+        if seconds > Self::MAX_SECONDS {
+            Self::MAX_SECONDS
+        } else {
+            seconds
+        }
+    }
+}
+
+impl Display for MaxAge {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "max-age={}", self.as_delta_seconds())
+    }
+}
+
+impl FromStr for MaxAge {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        for directive in split_directives(other) {
+            let directive = directive.trim_matches(is_whitespace);
+            let (name, value) = match directive.find('=') {
+                Some(i) => (&directive[..i], &directive[(i + 1)..]),
+                None => (directive, ""),
+            };
+            if !name.eq_ignore_ascii_case("max-age") {
+                continue;
+            }
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(DurationError);
+            }
+            let seconds = match value.parse::<u64>() {
+                Ok(seconds) if seconds <= Self::MAX_SECONDS => seconds,
+                _ => Self::MAX_SECONDS,
+            };
+            return Ok(Self(Duration(StdDuration::from_secs(seconds))));
+        }
+        Err(DurationError)
+    }
+}
+
+impl From<Duration> for MaxAge {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<MaxAge> for Duration {
+    fn from(other: MaxAge) -> Self {
+        other.0
+    }
+}
+
+impl Deref for MaxAge {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for MaxAge {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/**
+[`Retry-After`](https://www.rfc-editor.org/rfc/rfc9110#name-retry-after) HTTP header
+in delay-seconds form.
+
+Formatting produces the number of seconds.
+The duration is rounded up to the whole number of seconds
+so that the client never retries earlier than the configured delay.
+Durations that do not fit into `u64` after rounding are replaced with `u64::MAX` seconds.
+
+Parsing accepts one or more decimal digits.
+HTTP dates are not supported.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct RetryAfter(pub Duration);
+
+impl RetryAfter {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+
+    /// Returns the number of seconds that is sent in the header.
+    pub const fn as_delay_seconds(&self) -> u64 {
+        let seconds = self.0 .0.as_secs();
+        if self.0 .0.subsec_nanos() != 0 && seconds != u64::MAX {
+            seconds + 1
+        } else {
+            seconds
+        }
+    }
+}
+
+impl Display for RetryAfter {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_delay_seconds())
+    }
+}
+
+impl FromStr for RetryAfter {
+    type Err = DurationError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let seconds = parse_decimal(other).ok_or(DurationError)?;
+        Ok(Self(Duration(StdDuration::from_secs(seconds))))
+    }
+}
+
+impl From<Duration> for RetryAfter {
+    fn from(other: Duration) -> Self {
+        Self(other)
+    }
+}
+
+impl From<RetryAfter> for Duration {
+    fn from(other: RetryAfter) -> Self {
+        other.0
+    }
+}
+
+impl Deref for RetryAfter {
+    type Target = Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RetryAfter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/**
+[`Content-Length`](https://www.rfc-editor.org/rfc/rfc9110#name-content-length) HTTP header.
+
+Formatting produces the number of bytes.
+Parsing accepts one or more decimal digits.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct ContentLength(pub Size);
+
+impl ContentLength {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for ContentLength {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0 .0)
+    }
+}
+
+impl FromStr for ContentLength {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let size = parse_decimal(other).ok_or(SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl From<Size> for ContentLength {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<ContentLength> for Size {
+    fn from(other: ContentLength) -> Self {
+        other.0
+    }
+}
+
+impl Deref for ContentLength {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ContentLength {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<MaxAge> for http::HeaderValue {
+    fn from(other: MaxAge) -> Self {
+        let mut buf = crate::Buffer::<{ MaxAge::MAX_STRING_LEN }>::new();
+        let _ = core::fmt::Write::write_fmt(&mut buf, format_args!("{}", other));
+        // `max-age=` followed by digits is always a valid header value
+        unsafe { http::HeaderValue::from_maybe_shared_unchecked(buf) }
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<RetryAfter> for http::HeaderValue {
+    fn from(other: RetryAfter) -> Self {
+        other.as_delay_seconds().into()
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<ContentLength> for http::HeaderValue {
+    fn from(other: ContentLength) -> Self {
+        other.0 .0.into()
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<&http::HeaderValue> for MaxAge {
+    type Error = DurationError;
+    fn try_from(other: &http::HeaderValue) -> Result<Self, Self::Error> {
+        other.to_str().map_err(|_| DurationError)?.parse()
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<&http::HeaderValue> for RetryAfter {
+    type Error = DurationError;
+    fn try_from(other: &http::HeaderValue) -> Result<Self, Self::Error> {
+        other.to_str().map_err(|_| DurationError)?.parse()
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<&http::HeaderValue> for ContentLength {
+    type Error = SizeError;
+    fn try_from(other: &http::HeaderValue) -> Result<Self, Self::Error> {
+        other.to_str().map_err(|_| SizeError)?.parse()
+    }
+}
+
+/// Splits header value by commas that are not inside quoted strings.
+fn split_directives(s: &str) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+    let mut escaped = false;
+    s.split(move |ch| {
+        if escaped {
+            escaped = false;
+            return false;
+        }
+        match ch {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' => return !quoted,
+            _ => {}
+        }
+        false
+    })
+}
+
+/// Optional whitespace in HTTP headers.
+const fn is_whitespace(ch: char) -> bool {
+    matches!(ch, ' ' | '\t')
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_max_age_display() {
+        assert_eq!("max-age=0", max_age(0, 0).to_string());
+        assert_eq!("max-age=0", max_age(0, 999_999_999).to_string());
+        assert_eq!("max-age=3600", max_age(3600, 500_000_000).to_string());
+        assert_eq!("max-age=2147483648", max_age(1 << 31, 0).to_string());
+        assert_eq!("max-age=2147483648", max_age(u64::MAX, 0).to_string());
+        assert_eq!(
+            MaxAge::MAX_STRING_LEN,
+            max_age(u64::MAX, 999_999_999).to_string().len()
+        );
+    }
+
+    #[test]
+    fn test_max_age_parse() {
+        assert_eq!(max_age(3600, 0), "max-age=3600".parse().unwrap());
+        assert_eq!(max_age(3600, 0), "Max-Age=3600".parse().unwrap());
+        assert_eq!(max_age(3600, 0), "max-age=\"3600\"".parse().unwrap());
+        assert_eq!(
+            max_age(60, 0),
+            "public,max-age=60 , s-maxage=120".parse().unwrap()
+        );
+        assert_eq!(max_age(60, 0), "max-age=60, max-age=120".parse().unwrap());
+        assert_eq!(
+            max_age(60, 0),
+            "no-cache=\"a,max-age=5\", max-age=60".parse().unwrap()
+        );
+        assert_eq!(
+            max_age(60, 0),
+            r#"private="a\",max-age=5", max-age=60"#.parse().unwrap()
+        );
+        assert_eq!(max_age(1 << 31, 0), "max-age=2147483649".parse().unwrap());
+        assert_eq!(
+            max_age(1 << 31, 0),
+            "max-age=99999999999999999999999".parse().unwrap()
+        );
+        for s in [
+            "",
+            "public",
+            "s-maxage=60",
+            "max-age",
+            "max-age=",
+            "max-age=-1",
+            "max-age=1.5",
+            "max-age=1s",
+            "max-age = 60",
+            "max-age=\"60",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<MaxAge>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_after_display() {
+        assert_eq!("0", retry_after(0, 0).to_string());
+        assert_eq!("1", retry_after(0, 1).to_string());
+        assert_eq!("120", retry_after(120, 0).to_string());
+        assert_eq!("121", retry_after(120, 500_000_000).to_string());
+        assert_eq!(
+            "18446744073709551615",
+            retry_after(u64::MAX, 999_999_999).to_string()
+        );
+        assert_eq!(
+            RetryAfter::MAX_STRING_LEN,
+            retry_after(u64::MAX, 0).to_string().len()
+        );
+    }
+
+    #[test]
+    fn test_retry_after_parse() {
+        assert_eq!(retry_after(120, 0), "120".parse().unwrap());
+        assert_eq!(retry_after(0, 0), "0".parse().unwrap());
+        for s in [
+            "",
+            " 120",
+            "+120",
+            "1.5",
+            "120s",
+            "Fri, 31 Dec 1999 23:59:59 GMT",
+            "18446744073709551616",
+        ] {
+            assert_eq!(
+                "Err(DurationError)",
+                format!("{:?}", s.parse::<RetryAfter>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_length() {
+        assert_eq!("0", ContentLength(Size(0)).to_string());
+        assert_eq!("1024", ContentLength(Size(1024)).to_string());
+        assert_eq!(ContentLength(Size(1024)), "1024".parse().unwrap());
+        assert_eq!(
+            ContentLength::MAX_STRING_LEN,
+            ContentLength(Size(u64::MAX)).to_string().len()
+        );
+        for s in ["", "1k", "-1", "+1", " 1", "1, 1", "18446744073709551616"] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<ContentLength>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let seconds = u.int_in_range(0..=MaxAge::MAX_SECONDS)?;
+            let expected = max_age(seconds, 0);
+            let actual: MaxAge = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let expected = retry_after(u.arbitrary()?, 0);
+            let actual: RetryAfter = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let expected: ContentLength = u.arbitrary()?;
+            let actual: ContentLength = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_header_value() {
+        let value = http::HeaderValue::from(max_age(3600, 1));
+        assert_eq!("max-age=3600", value);
+        assert_eq!(max_age(3600, 0), MaxAge::try_from(&value).unwrap());
+        let value = http::HeaderValue::from(retry_after(120, 1));
+        assert_eq!("121", value);
+        assert_eq!(retry_after(121, 0), RetryAfter::try_from(&value).unwrap());
+        let value = http::HeaderValue::from(ContentLength(Size(1024)));
+        assert_eq!("1024", value);
+        assert_eq!(
+            ContentLength(Size(1024)),
+            ContentLength::try_from(&value).unwrap()
+        );
+        let value = http::HeaderValue::from_bytes(b"\xff").unwrap();
+        assert!(ContentLength::try_from(&value).is_err());
+    }
+
+    fn max_age(seconds: u64, nanoseconds: u32) -> MaxAge {
+        MaxAge(Duration(StdDuration::new(seconds, nanoseconds)))
+    }
+
+    fn retry_after(seconds: u64, nanoseconds: u32) -> RetryAfter {
+        RetryAfter(Duration(StdDuration::new(seconds, nanoseconds)))
+    }
+}
@@ -1,18 +1,134 @@
 #![cfg_attr(feature = "no_std", no_std)]
 #![doc = include_str!("../README.md")]
+#[cfg(any(
+    feature = "serde",
+    feature = "schemars",
+    feature = "clap",
+    feature = "arbitrary",
+    feature = "proptest",
+    feature = "rusqlite",
+    feature = "postgres-types"
+))]
+extern crate alloc;
+#[cfg(all(
+    any(
+        feature = "clap",
+        feature = "arbitrary",
+        feature = "proptest",
+        feature = "rusqlite",
+        feature = "postgres-types"
+    ),
+    feature = "no_std"
+))]
+extern crate std;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+mod bounded;
 mod buffer;
+mod cgroup;
 mod duration;
+#[cfg(feature = "chrono")]
+mod duration_chrono;
+#[cfg(feature = "clap")]
+mod duration_clap;
+mod duration_compose;
 mod duration_format;
+mod duration_humantime;
+#[cfg(feature = "jiff")]
+mod duration_jiff;
+mod duration_nginx;
+mod duration_postgres;
+mod duration_prometheus;
+#[cfg(feature = "schemars")]
+mod duration_schema;
 #[cfg(feature = "serde")]
 mod duration_serde;
+mod duration_systemd;
+#[cfg(feature = "time")]
+mod duration_time;
+mod error;
+mod header;
+mod limit;
+mod linux;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "serde")]
+pub mod serde;
 mod size;
+#[cfg(feature = "clap")]
+mod size_clap;
+mod size_compose;
+mod size_coreutils;
 mod size_format;
+mod size_jvm;
+mod size_kubernetes;
+mod size_nginx;
+mod size_postgres;
+mod size_relative;
+#[cfg(feature = "schemars")]
+mod size_schema;
 #[cfg(feature = "serde")]
 mod size_serde;
+#[cfg(feature = "postgres-types")]
+mod sql_postgres;
+#[cfg(feature = "rusqlite")]
+mod sql_sqlite;
+#[cfg(any(feature = "rusqlite", feature = "postgres-types"))]
+mod sql_text;
+#[cfg(feature = "clap")]
+mod str_clap;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod str_fuzz;
+#[cfg(feature = "schemars")]
+mod str_schema;
+#[cfg(feature = "serde")]
+mod str_serde;
 
+pub use self::bounded::*;
 //#[cfg(feature = "serde")]
 pub(crate) use self::buffer::*;
+pub use self::cgroup::*;
 pub use self::duration::*;
+#[cfg(feature = "clap")]
+pub use self::duration_clap::*;
+pub use self::duration_compose::*;
 pub use self::duration_format::*;
+pub use self::duration_humantime::*;
+pub use self::duration_nginx::*;
+pub use self::duration_postgres::*;
+pub use self::duration_prometheus::*;
+pub use self::duration_systemd::*;
+pub use self::error::*;
+pub use self::header::*;
+pub use self::limit::*;
+pub use self::linux::*;
 pub use self::size::*;
+#[cfg(feature = "clap")]
+pub use self::size_clap::*;
+pub use self::size_compose::*;
+pub use self::size_coreutils::*;
 pub use self::size_format::*;
+pub use self::size_jvm::*;
+pub use self::size_kubernetes::*;
+pub use self::size_nginx::*;
+pub use self::size_postgres::*;
+pub use self::size_relative::*;
+#[cfg(any(feature = "rusqlite", feature = "postgres-types"))]
+pub use self::sql_text::*;
+#[cfg(feature = "clap")]
+pub(crate) use self::str_clap::*;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) use self::str_fuzz::*;
+#[cfg(feature = "schemars")]
+pub(crate) use self::str_schema::*;
+#[cfg(feature = "serde")]
+pub(crate) use self::str_serde::*;
+
+/// Parses non-empty string of ASCII digits without a sign.
+pub(crate) fn parse_decimal(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
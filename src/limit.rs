@@ -0,0 +1,415 @@
+use core::fmt::Display;
+use core::str::FromStr;
+
+/**
+A value or no limit (`512m` or `unlimited`).
+
+Parsing accepts any string accepted by `T` as well as
+`unlimited`, `infinity`, `max`, `none` and `-1` that mean no limit.
+Formatting produces `unlimited` for no limit.
+
+[`Unlimited`](Limit::Unlimited) is greater than any finite value
+and is the default.
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+pub enum Limit<T> {
+    /// Finite value.
+    Finite(T),
+    /// No limit.
+    Unlimited,
+}
+
+impl<T> Limit<T> {
+    /// Returns `true` if there is no limit.
+    pub const fn is_unlimited(&self) -> bool {
+        matches!(self, Self::Unlimited)
+    }
+
+    /// Returns the finite value or `None` if there is no limit.
+    pub fn finite(self) -> Option<T> {
+        match self {
+            Self::Finite(value) => Some(value),
+            Self::Unlimited => None,
+        }
+    }
+}
+
+impl<T> Default for Limit<T> {
+    fn default() -> Self {
+        Self::Unlimited
+    }
+}
+
+impl<T: Display> Display for Limit<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Finite(value) => value.fmt(f),
+            Self::Unlimited => f.write_str(UNLIMITED),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for Limit<T> {
+    type Err = T::Err;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        if UNLIMITED_WORDS.contains(&other.trim()) {
+            return Ok(Self::Unlimited);
+        }
+        other.parse().map(Self::Finite)
+    }
+}
+
+impl<T> From<T> for Limit<T> {
+    fn from(other: T) -> Self {
+        Self::Finite(other)
+    }
+}
+
+impl<T> From<Option<T>> for Limit<T> {
+    fn from(other: Option<T>) -> Self {
+        match other {
+            Some(value) => Self::Finite(value),
+            None => Self::Unlimited,
+        }
+    }
+}
+
+impl<T> From<Limit<T>> for Option<T> {
+    fn from(other: Limit<T>) -> Self {
+        other.finite()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Limit<T> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match (self, s.is_human_readable()) {
+            (Self::Finite(value), true) => value.serialize(s),
+            (Self::Unlimited, true) => s.serialize_str(UNLIMITED),
+            (Self::Finite(value), false) => s.serialize_some(value),
+            (Self::Unlimited, false) => s.serialize_none(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T: serde::Deserialize<'a>> serde::Deserialize<'a> for Limit<T> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        if !d.is_human_readable() {
+            let value: Option<T> = serde::Deserialize::deserialize(d)?;
+            return Ok(value.into());
+        }
+        d.deserialize_any(LimitVisitor(core::marker::PhantomData))
+    }
+}
+
+/// Deserializes no-limit words and `-1` as [`Limit::Unlimited`] and passes other values to `T`.
+#[cfg(feature = "serde")]
+struct LimitVisitor<T>(core::marker::PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'a, T: serde::Deserialize<'a>> serde::de::Visitor<'a> for LimitVisitor<T> {
+    type Value = Limit<T>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a value or `unlimited`")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        use serde::de::IntoDeserializer;
+        if UNLIMITED_WORDS.contains(&value.trim()) {
+            return Ok(Limit::Unlimited);
+        }
+        T::deserialize(value.into_deserializer()).map(Limit::Finite)
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        use serde::de::IntoDeserializer;
+        T::deserialize(value.into_deserializer()).map(Limit::Finite)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        use serde::de::IntoDeserializer;
+        if value == -1 {
+            return Ok(Limit::Unlimited);
+        }
+        T::deserialize(value.into_deserializer()).map(Limit::Finite)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'a>,
+    {
+        T::deserialize(serde::de::value::MapAccessDeserializer::new(map)).map(Limit::Finite)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema> schemars::JsonSchema for Limit<T> {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("Limit_of_{}", T::schema_name()).into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        alloc::format!("human_units::Limit<{}>", T::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A value or no limit",
+            "anyOf": [
+                generator.subschema_for::<T>(),
+                {
+                    "type": "string",
+                    "pattern": r"^\s*(unlimited|infinity|max|none|-1)\s*$",
+                },
+                {"const": -1},
+            ],
+            "examples": [UNLIMITED],
+        })
+    }
+}
+
+const UNLIMITED: &str = "unlimited";
+const UNLIMITED_WORDS: [&str; 5] = [UNLIMITED, "infinity", "max", "none", "-1"];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use core::time::Duration as StdDuration;
+
+    use arbtest::arbtest;
+
+    use super::*;
+    use crate::Duration;
+    use crate::Size;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("unlimited", Limit::<Size>::Unlimited.to_string());
+        assert_eq!("1k", Limit::Finite(Size(1024)).to_string());
+        assert_eq!(
+            "1m",
+            Limit::Finite(Duration(StdDuration::from_secs(60))).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for s in ["unlimited", "infinity", "max", "none", "-1", " max\n"] {
+            assert_eq!(Limit::<Size>::Unlimited, s.parse().unwrap(), "s = `{}`", s);
+            assert_eq!(
+                Limit::<Duration>::Unlimited,
+                s.parse().unwrap(),
+                "s = `{}`",
+                s
+            );
+        }
+        assert_eq!(Limit::Finite(Size(512 << 20)), "512m".parse().unwrap());
+        assert_eq!(
+            Limit::Finite(Duration(StdDuration::from_secs(30))),
+            "30s".parse().unwrap()
+        );
+        for s in ["", "Unlimited", "inf", "-2", "max1"] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<Limit<Size>>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(Limit::Finite(Size(u64::MAX)) < Limit::Unlimited);
+        assert!(Limit::Finite(Size(0)) < Limit::Finite(Size(1)));
+        assert_eq!(
+            Limit::Finite(Size(1)),
+            Limit::Unlimited.min(Limit::Finite(Size(1)))
+        );
+        assert_eq!(Limit::<Size>::Unlimited, Limit::default());
+        assert_eq!(Some(Size(1)), Limit::from(Some(Size(1))).finite());
+        assert_eq!(None, Option::<Size>::from(Limit::<Size>::from(None)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_io() {
+        assert_eq!(
+            "\"unlimited\"",
+            serde_json::to_string(&Limit::<Size>::Unlimited).unwrap()
+        );
+        assert_eq!(
+            "\"1k\"",
+            serde_json::to_string(&Limit::Finite(Size(1024))).unwrap()
+        );
+        assert_eq!(
+            Limit::<Size>::Unlimited,
+            serde_json::from_str("\"infinity\"").unwrap()
+        );
+        assert_eq!(
+            Limit::Finite(Size(1024)),
+            serde_json::from_str("\"1k\"").unwrap()
+        );
+        assert_eq!(
+            Limit::<Size>::Unlimited,
+            serde_yaml::from_str("-1").unwrap()
+        );
+        assert_eq!(
+            Limit::<Size>::Unlimited,
+            serde_json::from_str("\"unlimited\"").unwrap()
+        );
+        assert_eq!(
+            Limit::<Size>::Unlimited,
+            serde_json::from_str("-1").unwrap()
+        );
+        assert_eq!(
+            Limit::Finite(Size(1048576)),
+            serde_json::from_str("1048576").unwrap()
+        );
+        assert_eq!(
+            Limit::Finite(Duration(StdDuration::new(1, 5))),
+            serde_json::from_str(r#"{"secs":1,"nanos":5}"#).unwrap()
+        );
+        assert_eq!(
+            Limit::Finite(Size(1024)),
+            toml::from_str::<LimitWrapper>("limit = 1024")
+                .unwrap()
+                .limit
+        );
+        for s in ["\"inf\"", "-2", "1.5", "null", "[]"] {
+            assert!(
+                serde_json::from_str::<Limit<Size>>(s).is_err(),
+                "s = `{}`",
+                s
+            );
+        }
+        assert_eq!(
+            "invalid value: string \"1x\", expected a size with optional unit k, m, g or t (unknown unit) at line 1 column 4",
+            serde_json::from_str::<Limit<Size>>("\"1x\"")
+                .unwrap_err()
+                .to_string()
+        );
+        for expected in [Limit::Finite(Size(1024)), Limit::Unlimited] {
+            let bytes = bincode::serialize(&expected).unwrap();
+            assert_eq!(expected, bincode::deserialize(&bytes).unwrap());
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<Limit<Size>>(
+            &[
+                "0",
+                "1",
+                "9",
+                "k",
+                "m",
+                "unlimited",
+                "infinity",
+                "inf",
+                "max",
+                "none",
+                "-1",
+                "-",
+                " ",
+                "\t",
+                "+",
+            ],
+            7,
+            |_| false,
+        );
+        crate::str_schema::tests::check_schema::<Limit<Duration>>(
+            &[
+                "0",
+                "1",
+                "9",
+                "ms",
+                "s",
+                "h",
+                "unlimited",
+                "infinity",
+                "max",
+                "none",
+                "-1",
+                "-",
+                " ",
+                "+",
+            ],
+            14,
+            |_| false,
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "schemars"))]
+    #[test]
+    fn test_schema_json() {
+        let schema = serde_json::to_value(schemars::schema_for!(Limit<Size>)).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for s in ["1048576", "-1", "\"unlimited\"", "-2", "1.5", "null"] {
+            assert_eq!(
+                serde_json::from_str::<Limit<Size>>(s).is_ok(),
+                validator.is_valid(&serde_json::from_str(s).unwrap()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_clap() {
+        use clap::Parser;
+
+        #[derive(Parser, Debug)]
+        struct Args {
+            #[arg(long, value_parser = clap::value_parser!(Limit<Size>))]
+            max_size: Limit<Size>,
+            #[arg(long, value_parser = clap::value_parser!(Limit<Duration>))]
+            timeout: Limit<Duration>,
+        }
+
+        let args = Args::parse_from(["test", "--max-size", "1g", "--timeout", "infinity"]);
+        assert_eq!(Limit::Finite(Size(1 << 30)), args.max_size);
+        assert_eq!(Limit::Unlimited, args.timeout);
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: Limit<Size> = u.arbitrary()?;
+            let actual: Limit<Size> = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let expected: Limit<Duration> = u.arbitrary()?;
+            let actual: Limit<Duration> = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(serde::Deserialize)]
+    struct LimitWrapper {
+        limit: Limit<Size>,
+    }
+}
@@ -0,0 +1,316 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+use core::str::Lines;
+
+use crate::parse_decimal;
+use crate::Size;
+use crate::SizeError;
+
+/**
+Iterator over `/proc/meminfo` entries that are sizes.
+
+Each line has the form `MemTotal:       16318440 kB` where `kB` means KiB.
+Lines without a unit (`HugePages_Total:       0`) are page counts, not sizes, and are skipped.
+The same format is used by `VmRSS`, `VmSize` etc. in `/proc/<pid>/status`.
+*/
+pub struct MeminfoEntries<'a> {
+    lines: Lines<'a>,
+}
+
+impl<'a> Iterator for MeminfoEntries<'a> {
+    type Item = Result<(&'a str, Size), SizeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in self.lines.by_ref() {
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = match line.find(':') {
+                Some(i) => (&line[..i], line[(i + 1)..].trim()),
+                None => return Some(Err(SizeError)),
+            };
+            let kibibytes = match value.strip_suffix(" kB") {
+                Some(kibibytes) => kibibytes.trim_end(),
+                None if value.bytes().all(|b| b.is_ascii_digit()) => continue,
+                None => return Some(Err(SizeError)),
+            };
+            return Some(parse_kibibytes(kibibytes).map(|size| (key, size)));
+        }
+        None
+    }
+}
+
+/// Returns an iterator over `/proc/meminfo` entries that are sizes.
+pub fn meminfo_entries(meminfo: &str) -> MeminfoEntries<'_> {
+    MeminfoEntries {
+        lines: meminfo.lines(),
+    }
+}
+
+/**
+Parses `/proc/meminfo` into a map of keys (`MemTotal`, `Active(anon)`) to sizes.
+
+See [`MeminfoEntries`] for the details.
+*/
+#[cfg(not(feature = "no_std"))]
+pub fn parse_meminfo(meminfo: &str) -> Result<std::collections::BTreeMap<&str, Size>, SizeError> {
+    meminfo_entries(meminfo).collect()
+}
+
+/**
+Exact size in cgroup v2 interface file format (`memory.current`, `memory.peak`, `memory.swap.current`).
+
+Parsing accepts the number of bytes with an optional trailing newline.
+Formatting produces the number of bytes without the newline.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct CgroupSize(pub Size);
+
+impl CgroupSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for CgroupSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0 .0)
+    }
+}
+
+impl FromStr for CgroupSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let size = parse_decimal(strip_newline(other)).ok_or(SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl From<Size> for CgroupSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<CgroupSize> for Size {
+    fn from(other: CgroupSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for CgroupSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CgroupSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/**
+Block device size in `/sys/block/<device>/size` format.
+
+The file contains the number of 512-byte sectors
+regardless of the logical block size of the device.
+
+Parsing accepts the number of sectors with an optional trailing newline.
+Formatting produces the number of sectors without the newline;
+sizes that are not a multiple of 512 bytes are rounded down.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct BlockDeviceSize(pub Size);
+
+impl BlockDeviceSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 17;
+
+    /// Sector size in bytes.
+    pub const SECTOR_SIZE: u64 = 512;
+}
+
+impl Display for BlockDeviceSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0 .0 / Self::SECTOR_SIZE)
+    }
+}
+
+impl FromStr for BlockDeviceSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let sectors = parse_decimal(strip_newline(other)).ok_or(SizeError)?;
+        let size = sectors.checked_mul(Self::SECTOR_SIZE).ok_or(SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl From<Size> for BlockDeviceSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<BlockDeviceSize> for Size {
+    fn from(other: BlockDeviceSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for BlockDeviceSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for BlockDeviceSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn parse_kibibytes(s: &str) -> Result<Size, SizeError> {
+    let kibibytes = parse_decimal(s).ok_or(SizeError)?;
+    let size = kibibytes.checked_mul(1024).ok_or(SizeError)?;
+    Ok(Size(size))
+}
+
+pub(crate) fn strip_newline(s: &str) -> &str {
+    s.strip_suffix('\n').unwrap_or(s)
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = parse_meminfo(include_str!("../fixtures/proc/meminfo")).unwrap();
+        assert_eq!(Some(&Size(6147400 * 1024)), meminfo.get("MemTotal"));
+        assert_eq!(Some(&Size(24 * 1024)), meminfo.get("Active(anon)"));
+        assert_eq!(Some(&Size(0)), meminfo.get("SwapTotal"));
+        assert_eq!(Some(&Size(34359738367 * 1024)), meminfo.get("VmallocTotal"));
+        assert_eq!(Some(&Size(2048 * 1024)), meminfo.get("Hugepagesize"));
+        assert_eq!(None, meminfo.get("HugePages_Total"));
+        assert_eq!(50, meminfo.len());
+    }
+
+    #[test]
+    fn test_meminfo_entries() {
+        let entries = meminfo_entries("MemTotal: 1 kB\n\nVmRSS:\t  2 kB\nHugePages_Free: 3\n")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![("MemTotal", Size(1024)), ("VmRSS", Size(2048))],
+            entries
+        );
+        for s in [
+            "MemTotal 1 kB",
+            "MemTotal: kB",
+            "MemTotal: 1 KB",
+            "MemTotal: 1 MB",
+            "MemTotal: -1 kB",
+            "MemTotal: 1.5 kB",
+            "MemTotal: 18014398509481984 kB",
+            "HugePages_Free: x",
+        ] {
+            assert_eq!(
+                "Some(Err(SizeError))",
+                format!("{:?}", meminfo_entries(s).next()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_cgroup_size() {
+        assert_eq!(
+            CgroupSize(Size(268435456)),
+            include_str!("../fixtures/sys/fs/cgroup/memory.current")
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            CgroupSize(Size(4294967296)),
+            include_str!("../fixtures/sys/fs/cgroup/memory.peak")
+                .parse()
+                .unwrap()
+        );
+        assert_eq!("1024", CgroupSize(Size(1024)).to_string());
+        for s in [
+            "",
+            "\n",
+            "max",
+            "1k",
+            " 1",
+            "1\n\n",
+            "-1",
+            "18446744073709551616",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<CgroupSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_block_device_size() {
+        assert_eq!(
+            BlockDeviceSize(Size(20 * 1024 * 1024 * 1024)),
+            include_str!("../fixtures/sys/block/vda/size")
+                .parse()
+                .unwrap()
+        );
+        assert_eq!("2", BlockDeviceSize(Size(1024)).to_string());
+        assert_eq!("2", BlockDeviceSize(Size(1535)).to_string());
+        assert_eq!(
+            BlockDeviceSize::MAX_STRING_LEN,
+            BlockDeviceSize(Size(u64::MAX)).to_string().len()
+        );
+        for s in ["", "\n", "1 ", "0x10", "36028797018963968"] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<BlockDeviceSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: CgroupSize = u.arbitrary()?;
+            let actual: CgroupSize = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            let sectors = u.int_in_range(0..=u64::MAX / BlockDeviceSize::SECTOR_SIZE)?;
+            let expected = BlockDeviceSize(Size(sectors * BlockDeviceSize::SECTOR_SIZE));
+            let actual: BlockDeviceSize = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+}
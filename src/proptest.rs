@@ -0,0 +1,144 @@
+//! Strategies for property testing with [`proptest`](https://docs.rs/proptest/latest/proptest/).
+//!
+//! [`Size`] and [`Duration`] implement [`Arbitrary`](::proptest::arbitrary::Arbitrary)
+//! and can be generated with `any::<Size>()` and `any::<Duration>()`.
+//! Valid strings cover every unit spelling, optional `+` sign and whitespace
+//! around the number and the unit; they are always accepted by `FromStr` implementation.
+//! Nearly valid strings are valid strings with a few characters removed or inserted;
+//! they may or may not be accepted.
+//!
+//! ```rust
+//! use human_units::Size;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn parse_size(s in human_units::proptest::size_str()) {
+//!         prop_assert!(s.parse::<Size>().is_ok());
+//!     }
+//! }
+//!
+//! parse_size();
+//! ```
+
+use alloc::string::String;
+use alloc::string::ToString;
+use core::time::Duration as StdDuration;
+
+use ::proptest::prelude::*;
+
+use crate::mutate;
+use crate::Duration;
+use crate::Size;
+use crate::ValidStr;
+use crate::DURATION_SPELLINGS;
+use crate::DURATION_TOKENS;
+use crate::SIZE_SPELLINGS;
+use crate::SIZE_TOKENS;
+use crate::WHITESPACE;
+
+impl Arbitrary for Size {
+    type Parameters = ();
+    type Strategy = prop::strategy::Map<prop::num::u64::Any, fn(u64) -> Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<u64>().prop_map(Size)
+    }
+}
+
+impl Arbitrary for Duration {
+    type Parameters = ();
+    type Strategy =
+        prop::strategy::Map<(prop::num::u64::Any, core::ops::Range<u32>), fn((u64, u32)) -> Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u64>(), 0..1_000_000_000_u32)
+            .prop_map(|(secs, nanos)| Duration(StdDuration::new(secs, nanos)))
+    }
+}
+
+/// Generates a string that is accepted by [`Size::from_str`](crate::Size).
+pub fn size_str() -> impl Strategy<Value = String> {
+    valid_str(&SIZE_SPELLINGS)
+}
+
+/// Generates a string that is similar to the ones accepted by [`Size::from_str`](crate::Size).
+pub fn nearly_valid_size_str() -> impl Strategy<Value = String> {
+    nearly_valid_str(&SIZE_SPELLINGS, &SIZE_TOKENS)
+}
+
+/// Generates a string that is accepted by [`Duration::from_str`](crate::Duration).
+pub fn duration_str() -> impl Strategy<Value = String> {
+    valid_str(&DURATION_SPELLINGS)
+}
+
+/// Generates a string that is similar to the ones accepted by [`Duration::from_str`](crate::Duration).
+pub fn nearly_valid_duration_str() -> impl Strategy<Value = String> {
+    nearly_valid_str(&DURATION_SPELLINGS, &DURATION_TOKENS)
+}
+
+fn valid_str(spellings: &'static [(&'static str, u128)]) -> impl Strategy<Value = String> {
+    let whitespace = || prop::sample::select(&WHITESPACE[..]);
+    prop::sample::select(spellings).prop_flat_map(move |(unit, max)| {
+        (
+            any::<bool>(),
+            whitespace(),
+            0..=max,
+            whitespace(),
+            whitespace(),
+        )
+            .prop_map(move |(sign, prefix, number, infix, suffix)| {
+                ValidStr {
+                    sign,
+                    prefix,
+                    number,
+                    infix,
+                    unit,
+                    suffix,
+                }
+                .to_string()
+            })
+    })
+}
+
+fn nearly_valid_str(
+    spellings: &'static [(&'static str, u128)],
+    tokens: &'static [&'static str],
+) -> impl Strategy<Value = String> {
+    let edit = (
+        any::<usize>(),
+        prop::option::of(prop::sample::select(tokens)),
+    );
+    (valid_str(spellings), prop::collection::vec(edit, 1..=3)).prop_map(|(mut s, edits)| {
+        for (index, token) in edits {
+            mutate(&mut s, index, token);
+        }
+        s
+    })
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_valid_str(size in size_str(), duration in duration_str()) {
+            prop_assert!(size.parse::<Size>().is_ok(), "size = `{}`", size);
+            prop_assert!(duration.parse::<Duration>().is_ok(), "duration = `{}`", duration);
+        }
+
+        #[test]
+        fn test_nearly_valid_str(size in nearly_valid_size_str(), duration in nearly_valid_duration_str()) {
+            // must not panic
+            let _ = size.parse::<Size>();
+            let _ = duration.parse::<Duration>();
+        }
+
+        #[test]
+        fn display_parse_symmetry(size in any::<Size>(), duration in any::<Duration>()) {
+            prop_assert_eq!(size, size.to_string().parse::<Size>().unwrap());
+            prop_assert_eq!(duration, duration.to_string().parse::<Duration>().unwrap());
+        }
+    }
+}
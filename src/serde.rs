@@ -0,0 +1,237 @@
+//! Modules for `#[serde(with = "...")]` attribute.
+//!
+//! These modules serialize plain `u64` and [`core::time::Duration`] fields
+//! in the same way as [`Size`](crate::Size) and [`Duration`](crate::Duration)
+//! without changing the field types.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "human_units::serde::size")]
+//!     cache_size: u64,
+//!     #[serde(with = "human_units::serde::duration::option")]
+//!     timeout: Option<core::time::Duration>,
+//! }
+//!
+//! let config: Config = toml::from_str("cache_size = \"1g\"\ntimeout = \"5m\"").unwrap();
+//! assert_eq!(1024_u64.pow(3), config.cache_size);
+//! assert_eq!(Some(core::time::Duration::from_secs(300)), config.timeout);
+//! ```
+
+/// Serializes `u64` as [`Size`](crate::Size).
+pub mod size {
+    use crate::Size;
+
+    /// Serializes the number of bytes.
+    pub fn serialize<S>(value: &u64, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        ::serde::Serialize::serialize(&Size(*value), s)
+    }
+
+    /// Deserializes the number of bytes.
+    pub fn deserialize<'a, D>(d: D) -> Result<u64, D::Error>
+    where
+        D: ::serde::Deserializer<'a>,
+    {
+        let size: Size = ::serde::Deserialize::deserialize(d)?;
+        Ok(size.0)
+    }
+
+    /// Serializes `Option<u64>` as `Option<Size>`.
+    pub mod option {
+        use crate::Size;
+
+        /// Serializes the number of bytes if any.
+        pub fn serialize<S>(value: &Option<u64>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+        {
+            ::serde::Serialize::serialize(&value.map(Size), s)
+        }
+
+        /// Deserializes the number of bytes if any.
+        pub fn deserialize<'a, D>(d: D) -> Result<Option<u64>, D::Error>
+        where
+            D: ::serde::Deserializer<'a>,
+        {
+            let size: Option<Size> = ::serde::Deserialize::deserialize(d)?;
+            Ok(size.map(|size| size.0))
+        }
+    }
+
+    /// Serializes `Vec<u64>` as `Vec<Size>`.
+    pub mod vec {
+        use alloc::vec::Vec;
+
+        use crate::Size;
+
+        /// Serializes the numbers of bytes.
+        pub fn serialize<S>(values: &[u64], s: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+        {
+            s.collect_seq(values.iter().map(|value| Size(*value)))
+        }
+
+        /// Deserializes the numbers of bytes.
+        pub fn deserialize<'a, D>(d: D) -> Result<Vec<u64>, D::Error>
+        where
+            D: ::serde::Deserializer<'a>,
+        {
+            let sizes: Vec<Size> = ::serde::Deserialize::deserialize(d)?;
+            Ok(sizes.into_iter().map(|size| size.0).collect())
+        }
+    }
+}
+
+/// Serializes [`core::time::Duration`] as [`Duration`](crate::Duration).
+pub mod duration {
+    use core::time::Duration as StdDuration;
+
+    use crate::Duration;
+
+    /// Serializes the duration.
+    pub fn serialize<S>(value: &StdDuration, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        ::serde::Serialize::serialize(&Duration(*value), s)
+    }
+
+    /// Deserializes the duration.
+    pub fn deserialize<'a, D>(d: D) -> Result<StdDuration, D::Error>
+    where
+        D: ::serde::Deserializer<'a>,
+    {
+        let duration: Duration = ::serde::Deserialize::deserialize(d)?;
+        Ok(duration.0)
+    }
+
+    /// Serializes `Option<core::time::Duration>` as `Option<Duration>`.
+    pub mod option {
+        use core::time::Duration as StdDuration;
+
+        use crate::Duration;
+
+        /// Serializes the duration if any.
+        pub fn serialize<S>(value: &Option<StdDuration>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+        {
+            ::serde::Serialize::serialize(&value.map(Duration), s)
+        }
+
+        /// Deserializes the duration if any.
+        pub fn deserialize<'a, D>(d: D) -> Result<Option<StdDuration>, D::Error>
+        where
+            D: ::serde::Deserializer<'a>,
+        {
+            let duration: Option<Duration> = ::serde::Deserialize::deserialize(d)?;
+            Ok(duration.map(|duration| duration.0))
+        }
+    }
+
+    /// Serializes `Vec<core::time::Duration>` as `Vec<Duration>`.
+    pub mod vec {
+        use alloc::vec::Vec;
+        use core::time::Duration as StdDuration;
+
+        use crate::Duration;
+
+        /// Serializes the durations.
+        pub fn serialize<S>(values: &[StdDuration], s: S) -> Result<S::Ok, S::Error>
+        where
+            S: ::serde::Serializer,
+        {
+            s.collect_seq(values.iter().map(|value| Duration(*value)))
+        }
+
+        /// Deserializes the durations.
+        pub fn deserialize<'a, D>(d: D) -> Result<Vec<StdDuration>, D::Error>
+        where
+            D: ::serde::Deserializer<'a>,
+        {
+            let durations: Vec<Duration> = ::serde::Deserialize::deserialize(d)?;
+            Ok(durations.into_iter().map(|duration| duration.0).collect())
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use core::time::Duration as StdDuration;
+
+    use arbtest::arbtest;
+
+    #[test]
+    fn test_serde_io() {
+        let expected = Config {
+            size: 1024,
+            size_option: Some(1 << 20),
+            size_vec: vec![1, 1 << 30],
+            duration: StdDuration::from_secs(300),
+            duration_option: None,
+            duration_vec: vec![StdDuration::from_millis(1), StdDuration::from_secs(3600)],
+        };
+        let string = r#"{"size":"1k","size_option":"1m","size_vec":["1","1g"],"duration":"5m","duration_option":null,"duration_vec":["1ms","1h"]}"#;
+        assert_eq!(string, serde_json::to_string(&expected).unwrap());
+        assert_eq!(expected, serde_json::from_str(string).unwrap());
+        let string = r#"{"size":1024,"size_option":null,"size_vec":[],"duration":300,"duration_option":"1s","duration_vec":[1]}"#;
+        assert_eq!(
+            Config {
+                size: 1024,
+                size_option: None,
+                size_vec: vec![],
+                duration: StdDuration::from_secs(300),
+                duration_option: Some(StdDuration::from_secs(1)),
+                duration_vec: vec![StdDuration::from_secs(1)],
+            },
+            serde_json::from_str(string).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serde_json() {
+        arbtest(|u| {
+            let expected: Config = u.arbitrary()?;
+            let string = serde_json::to_string(&expected).unwrap();
+            let actual = serde_json::from_str(&string).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_serde_bincode() {
+        arbtest(|u| {
+            let expected: Config = u.arbitrary()?;
+            let bytes = bincode::serialize(&expected).unwrap();
+            let actual = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[derive(
+        serde::Serialize, serde::Deserialize, arbitrary::Arbitrary, Debug, PartialEq, Eq, Clone,
+    )]
+    struct Config {
+        #[serde(with = "crate::serde::size")]
+        size: u64,
+        #[serde(with = "crate::serde::size::option")]
+        size_option: Option<u64>,
+        #[serde(with = "crate::serde::size::vec")]
+        size_vec: Vec<u64>,
+        #[serde(with = "crate::serde::duration")]
+        duration: StdDuration,
+        #[serde(with = "crate::serde::duration::option")]
+        duration_option: Option<StdDuration>,
+        #[serde(with = "crate::serde::duration::vec")]
+        duration_vec: Vec<StdDuration>,
+    }
+}
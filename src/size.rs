@@ -6,6 +6,8 @@ use core::ops::Deref;
 use core::ops::DerefMut;
 use core::str::FromStr;
 
+use crate::ErrorKind;
+
 /**
 Exact size in bytes.
 
@@ -13,7 +15,10 @@ The intended use is the configuration files where exact numbers are required,
 i.e. cache size, maximum HTTP body size etc.
 */
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(all(test, not(feature = "no_std")), derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
 #[repr(transparent)]
 pub struct Size(pub u64);
 
@@ -44,26 +49,34 @@ impl Display for Size {
     }
 }
 
-impl FromStr for Size {
-    type Err = SizeError;
-    fn from_str(other: &str) -> Result<Self, Self::Err> {
+impl Size {
+    /// Parses the size returning the reason of the failure.
+    pub(crate) fn parse(other: &str) -> Result<Self, ErrorKind> {
         let other = other.trim();
         match other.rfind(char::is_numeric) {
-            None => Err(SizeError),
+            None => Err(ErrorKind::NoNumber),
             Some(i) => {
-                let size: u64 = other[..=i].parse().map_err(|_| SizeError)?;
+                let size: u64 = ErrorKind::parse_integer(&other[..=i])?;
                 let unit = other[(i + 1)..].trim();
                 let factor = match unit.len() {
                     0 => 1_u64,
-                    1 => unit_to_factor(unit.as_bytes()[0])?,
-                    _ => return Err(SizeError),
+                    1 => unit_to_factor(unit.as_bytes()[0]).map_err(|_| ErrorKind::UnknownUnit)?,
+                    _ => return Err(ErrorKind::UnknownUnit),
                 };
-                Ok(Self(size * factor))
+                let size = size.checked_mul(factor).ok_or(ErrorKind::Overflow)?;
+                Ok(Self(size))
             }
         }
     }
 }
 
+impl FromStr for Size {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        Self::parse(other).map_err(|_| SizeError)
+    }
+}
+
 impl From<u64> for Size {
     fn from(other: u64) -> Self {
         Self(other)
@@ -120,6 +133,10 @@ const UNITS: [(NonZeroU16, &str); 4] = [
     (unsafe { NonZeroU16::new_unchecked(1024) }, "t"),
 ];
 
+/// Supported units in error messages.
+#[cfg(any(feature = "serde", feature = "clap"))]
+pub(crate) const SIZE_UNITS: &str = "k, m, g or t";
+
 #[cfg(all(test, not(feature = "no_std")))]
 mod tests {
 
@@ -0,0 +1,246 @@
+use core::ops::Bound;
+use core::ops::RangeBounds;
+use std::ffi::OsStr;
+
+use crate::ExpectedUnits;
+use crate::Size;
+use crate::SIZE_UNITS;
+
+/**
+Command line argument parser for [`Size`].
+
+`clap::value_parser!(Size)` returns this parser that accepts any size.
+Use [`range`](SizeValueParser::range) to restrict the accepted values.
+
+```rust
+use clap::Parser;
+use human_units::{Size, SizeValueParser};
+
+#[derive(Parser)]
+struct Args {
+    #[arg(
+        long,
+        value_name = SizeValueParser::VALUE_NAME,
+        help = SizeValueParser::HELP,
+        value_parser = SizeValueParser::new().range(Size(1024)..=Size(1 << 30)),
+    )]
+    cache_size: Size,
+}
+
+let args = Args::try_parse_from(["test", "--cache-size", "512m"]).unwrap();
+assert_eq!(Size(512 << 20), args.cache_size);
+assert!(Args::try_parse_from(["test", "--cache-size", "2g"]).is_err());
+```
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SizeValueParser {
+    min: u64,
+    max: u64,
+}
+
+impl SizeValueParser {
+    /// Value name that is shown in the usage.
+    pub const VALUE_NAME: &'static str = "SIZE";
+
+    /// Help snippet that describes the format.
+    pub const HELP: &'static str =
+        "Size with optional unit k, m, g or t (powers of 1024), e.g. 512m";
+
+    /// Creates the parser that accepts any size.
+    pub const fn new() -> Self {
+        Self {
+            min: 0,
+            max: u64::MAX,
+        }
+    }
+
+    /// Accepts only the sizes from the `range`.
+    ///
+    /// The parser rejects any size if the range is empty.
+    pub fn range<B: RangeBounds<Size>>(self, range: B) -> Self {
+        let min = match range.start_bound() {
+            Bound::Included(min) => Some(min.0),
+            Bound::Excluded(min) => min.0.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let max = match range.end_bound() {
+            Bound::Included(max) => Some(max.0),
+            Bound::Excluded(max) => max.0.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+        match (min, max) {
+            (Some(min), Some(max)) if min <= max => Self { min, max },
+            _ => Self::EMPTY,
+        }
+    }
+
+    /// The parser that rejects any size.
+    const EMPTY: Self = Self { min: 1, max: 0 };
+}
+
+impl Default for SizeValueParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl clap::builder::TypedValueParser for SizeValueParser {
+    type Value = Size;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = crate::clap_str(cmd, value)?;
+        let size = Size::parse(value).map_err(|reason| {
+            crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                ExpectedUnits {
+                    what: "a size",
+                    units: SIZE_UNITS,
+                    reason,
+                },
+            )
+        })?;
+        if *self == Self::EMPTY {
+            return Err(crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                "a size from an empty range",
+            ));
+        }
+        if !(self.min..=self.max).contains(&size.0) {
+            return Err(crate::clap_invalid_value(
+                cmd,
+                arg,
+                value,
+                format_args!("a size from {} to {}", Size(self.min), Size(self.max)),
+            ));
+        }
+        Ok(size)
+    }
+}
+
+impl clap::builder::ValueParserFactory for Size {
+    type Parser = SizeValueParser;
+
+    fn value_parser() -> Self::Parser {
+        SizeValueParser::new()
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use clap::builder::TypedValueParser;
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser, Debug)]
+    struct Args {
+        #[arg(
+            long,
+            value_name = SizeValueParser::VALUE_NAME,
+            help = SizeValueParser::HELP,
+            value_parser = clap::value_parser!(Size),
+        )]
+        size: Option<Size>,
+        #[arg(long, value_parser = SizeValueParser::new().range(Size(1024)..Size(1 << 30)))]
+        buffer: Option<Size>,
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = Args::try_parse_from(["test", "--size", "1g", "--buffer", "1k"]).unwrap();
+        assert_eq!(Some(Size(1 << 30)), args.size);
+        assert_eq!(Some(Size(1024)), args.buffer);
+        let args = Args::try_parse_from(["test", "--buffer", "1073741823"]).unwrap();
+        assert_eq!(Some(Size((1 << 30) - 1)), args.buffer);
+    }
+
+    #[test]
+    fn test_errors() {
+        for (args, error) in [
+            (
+                ["test", "--size", "10x"],
+                "error: invalid value '10x' for '--size <SIZE>': expected a size with optional unit k, m, g or t (unknown unit)\n",
+            ),
+            (
+                ["test", "--size", "+-1"],
+                "error: invalid value '+-1' for '--size <SIZE>': expected a size with optional unit k, m, g or t (not a non-negative integer)\n",
+            ),
+            (
+                ["test", "--buffer", "1023"],
+                "error: invalid value '1023' for '--buffer <BUFFER>': expected a size from 1k to 1073741823\n",
+            ),
+            (
+                ["test", "--buffer", "1g"],
+                "error: invalid value '1g' for '--buffer <BUFFER>': expected a size from 1k to 1073741823\n",
+            ),
+        ] {
+            let actual = Args::try_parse_from(args).unwrap_err();
+            assert_eq!(clap::error::ErrorKind::ValueValidation, actual.kind());
+            let actual = actual.to_string();
+            assert!(actual.starts_with(error), "actual = `{}`", actual);
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(SizeValueParser::new(), SizeValueParser::new().range(..));
+        assert_eq!(
+            SizeValueParser { min: 1, max: 9 },
+            SizeValueParser::new().range(Size(1)..Size(10))
+        );
+        assert_eq!(
+            SizeValueParser { min: 1, max: 10 },
+            SizeValueParser::new().range(Size(1)..=Size(10))
+        );
+        assert_eq!(
+            SizeValueParser {
+                min: 1,
+                max: u64::MAX
+            },
+            SizeValueParser::new().range(Size(1)..)
+        );
+        let cmd = clap::Command::new("test");
+        for (parser, value) in [
+            (SizeValueParser::new().range(..Size(0)), "0"),
+            (
+                SizeValueParser::new().range((Bound::Excluded(Size(u64::MAX)), Bound::Unbounded)),
+                "18446744073709551615",
+            ),
+            (SizeValueParser::new().range(Size(2)..=Size(1)), "1"),
+        ] {
+            let error = parser
+                .parse_ref(&cmd, None, OsStr::new(value))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                error.contains("expected a size from an empty range"),
+                "value = `{}`, error = `{}`",
+                value,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_help() {
+        let help = Args::try_parse_from(["test", "--help"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            help.lines()
+                .any(|line| line.contains("--size <SIZE>") && line.ends_with(SizeValueParser::HELP)),
+            "help = `{}`",
+            help
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in [Compose file](https://github.com/compose-spec/compose-spec/blob/main/11-extension.md#specifying-byte-values)
+and Docker CLI format (`mem_limit: 1gb`, `--memory 512m`).
+
+Parsing follows `RAMInBytes` from Docker's `go-units`:
+the number is a non-negative decimal with at least one digit before and after the decimal point,
+the suffix is `b` or one of `k`, `m`, `g`, `t`, `p` optionally followed by `b` or `ib`,
+suffixes are case-insensitive powers of 1024,
+a single space is allowed between the number and the suffix,
+fractions (`1.5g`) are truncated to the whole number of bytes,
+and sizes larger than `i64::MAX` are rejected.
+
+Formatting uses the largest of `gb`, `mb`, `kb` suffixes that divides the size exactly
+and `b` otherwise.
+Sizes larger than `i64::MAX` are formatted as is and hence can't be parsed back.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct ComposeSize(pub Size);
+
+impl ComposeSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 21;
+}
+
+impl Display for ComposeSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut size = self.0 .0;
+        let mut unit = "b";
+        if size != 0 {
+            for u in UNITS {
+                if size % 1024 != 0 {
+                    break;
+                }
+                size /= 1024;
+                unit = u;
+            }
+        }
+        write!(f, "{}{}", size, unit)
+    }
+}
+
+impl FromStr for ComposeSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let number_len = other
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(other.len());
+        let (number, suffix) = other.split_at(number_len);
+        let suffix = suffix.strip_prefix(' ').unwrap_or(suffix);
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(..) => return Err(SizeError),
+            None => (number, ""),
+        };
+        if integer.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(SizeError);
+        }
+        let shift = match suffix.as_bytes() {
+            [] => 0,
+            [b] if b.eq_ignore_ascii_case(&b'b') => 0,
+            [u, rest @ ..] => {
+                let shift = match u.to_ascii_lowercase() {
+                    b'k' => 10,
+                    b'm' => 20,
+                    b'g' => 30,
+                    b't' => 40,
+                    b'p' => 50,
+                    _ => return Err(SizeError),
+                };
+                if !(rest.is_empty()
+                    || rest.eq_ignore_ascii_case(b"b")
+                    || rest.eq_ignore_ascii_case(b"ib"))
+                {
+                    return Err(SizeError);
+                }
+                shift
+            }
+        };
+        let mut size: u128 = 0;
+        for digit in integer.bytes().chain(fraction.bytes()) {
+            size = size
+                .checked_mul(10)
+                .and_then(|size| size.checked_add((digit - b'0') as u128))
+                .ok_or(SizeError)?;
+        }
+        let size = size.checked_mul(1 << shift).ok_or(SizeError)?;
+        let denominator = 10_u128
+            .checked_pow(fraction.len() as u32)
+            .ok_or(SizeError)?;
+        let size = size / denominator;
+        if size > i64::MAX as u128 {
+            return Err(SizeError);
+        }
+        Ok(Self(Size(size as u64)))
+    }
+}
+
+impl From<Size> for ComposeSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<ComposeSize> for Size {
+    fn from(other: ComposeSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for ComposeSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ComposeSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ComposeSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ ComposeSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for ComposeSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a Compose byte value")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ComposeSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "ComposeSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::ComposeSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in Compose file format with optional unit b, k, m, g, t or p optionally followed by b or ib (powers of 1024)",
+            r"^[0-9]+(\.[0-9]+)? ?([bB]|[kKmMgGtTpP]([bB]|[iI][bB])?)?$",
+            &["1gb", "512m", "1.5g"],
+        )
+    }
+}
+
+const UNITS: [&str; 3] = ["kb", "mb", "gb"];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0b", compose(0).to_string());
+        assert_eq!("2b", compose(2).to_string());
+        assert_eq!("1025b", compose(1025).to_string());
+        assert_eq!("1kb", compose(1024).to_string());
+        assert_eq!("300mb", compose(300 * 1024 * 1024).to_string());
+        assert_eq!("1gb", compose(1024 * 1024 * 1024).to_string());
+        assert_eq!("1024gb", compose(1 << 40).to_string());
+        assert_eq!(
+            ComposeSize::MAX_STRING_LEN,
+            compose(u64::MAX).to_string().len()
+        );
+        assert!(compose(i64::MAX as u64 + 1)
+            .to_string()
+            .parse::<ComposeSize>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(compose(1024 * 1024 * 1024), "1gb".parse().unwrap());
+        assert_eq!(compose(1024 * 1024 * 1024), "1GB".parse().unwrap());
+        assert_eq!(compose(1024 * 1024 * 1024), "1GiB".parse().unwrap());
+        assert_eq!(compose(512 * 1024 * 1024), "512m".parse().unwrap());
+        assert_eq!(compose(100 * 1024 * 1024), "100mb".parse().unwrap());
+        assert_eq!(compose(100 * 1024 * 1024), "100 mb".parse().unwrap());
+        assert_eq!(compose(2), "2b".parse().unwrap());
+        assert_eq!(compose(2), "2".parse().unwrap());
+        assert_eq!(compose(2), "2 ".parse().unwrap());
+        assert_eq!(compose(1 << 40), "1t".parse().unwrap());
+        assert_eq!(compose(1 << 50), "1p".parse().unwrap());
+        assert_eq!(compose(1536), "1.5k".parse().unwrap());
+        assert_eq!(compose(1), "1.9".parse().unwrap());
+        assert_eq!(compose(512), "0.5kb".parse().unwrap());
+        assert_eq!(
+            compose(i64::MAX as u64),
+            "9223372036854775807".parse().unwrap()
+        );
+        for s in [
+            "",
+            ".",
+            "b",
+            "kb",
+            " 1k",
+            "1  k",
+            "1k ",
+            "-1k",
+            "1bb",
+            "1kib ",
+            "1kbb",
+            "1e3",
+            "1.2.3k",
+            "+1g",
+            ".5g",
+            "1.",
+            "1.k",
+            "1x",
+            "1i",
+            "8192p",
+            "9223372036854775808",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<ComposeSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<ComposeSize>(
+            &[
+                "0", "1", "9", "10", "b", "B", "k", "M", "g", "t", "P", "i", "I", "ib", "x", " ",
+                "+", ".", "-",
+            ],
+            3,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected = compose(u.int_in_range(0..=i64::MAX as u64)?);
+            let string = expected.to_string();
+            let actual: ComposeSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn compose(size: u64) -> ComposeSize {
+        ComposeSize(Size(size))
+    }
+}
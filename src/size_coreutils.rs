@@ -0,0 +1,283 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::is_space;
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in GNU coreutils format (`head -c 1K`, `dd bs=4MB`, `sort -S 1GiB`).
+
+Parsing follows `xstrtoumax` from gnulib with the suffixes accepted by `head -c`:
+`K`, `M`, `G`, `T`, `P`, `E`, `Z`, `Y` (and their undocumented lower-case forms `k` and `m`)
+are powers of 1024 when used alone or followed by `iB` (`1K`, `1KiB`),
+and powers of 1000 when followed by `B` or `D` (`1KB`),
+`b` is 512 bytes,
+a suffix without a number means one unit (`K` is 1024),
+whitespace is allowed before the number but not after it,
+and fractions are not allowed.
+
+Formatting uses the largest of `K`, `M`, `G`, `T`, `P`, `E` suffixes that divides the size exactly,
+then the largest of `KB`, `MB`, `GB`, `TB`, `PB`, `EB`,
+and no suffix otherwise.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct CoreutilsSize(pub Size);
+
+impl CoreutilsSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for CoreutilsSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let size = self.0 .0;
+        if size != 0 {
+            for (base, suffix) in [(1024, ""), (1000, "B")] {
+                let mut n = size;
+                let mut power = 0;
+                while n % base == 0 && power < POWERS.len() {
+                    n /= base;
+                    power += 1;
+                }
+                if power != 0 {
+                    return write!(f, "{}{}{}", n, POWERS[power - 1] as char, suffix);
+                }
+            }
+        }
+        write!(f, "{}", size)
+    }
+}
+
+impl FromStr for CoreutilsSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let number = other.trim_start_matches(is_space);
+        let number = number.strip_prefix('+').unwrap_or(number);
+        let digits_len = number
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(number.len());
+        let (digits, suffix) = number.split_at(digits_len);
+        let size: u64 = if !digits.is_empty() {
+            digits.parse().map_err(|_| SizeError)?
+        } else if number.len() == other.len() && !suffix.is_empty() {
+            // the suffix without a number means one unit
+            1
+        } else {
+            return Err(SizeError);
+        };
+        let factor: u128 = match suffix.as_bytes() {
+            [] => 1,
+            [b'b'] => 512,
+            [unit, rest @ ..] => {
+                let power = POWERS
+                    .iter()
+                    .position(|p| p == unit)
+                    .or(match unit {
+                        b'k' => Some(0),
+                        b'm' => Some(1),
+                        _ => None,
+                    })
+                    .ok_or(SizeError)?;
+                let base: u128 = match rest {
+                    [] | b"iB" => 1024,
+                    b"B" | b"D" => 1000,
+                    _ => return Err(SizeError),
+                };
+                base.pow(power as u32 + 1)
+            }
+        };
+        let size = (size as u128)
+            .checked_mul(factor)
+            .and_then(|size| u64::try_from(size).ok())
+            .ok_or(SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl From<Size> for CoreutilsSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<CoreutilsSize> for Size {
+    fn from(other: CoreutilsSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for CoreutilsSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CoreutilsSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CoreutilsSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ CoreutilsSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for CoreutilsSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a coreutils size")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CoreutilsSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "CoreutilsSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::CoreutilsSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in GNU coreutils format with optional unit K, M, G, T, P, E, Z or Y (powers of 1024) optionally followed by iB, or followed by B (powers of 1000)",
+            r"^([ \t\n\x0B\x0C\r]*\+?[0-9]+(b|[KMGTPEZYkm](iB|B|D)?)?|b|[KMGTPEZYkm](iB|B|D)?)$",
+            &["1K", "4MB", "1GiB"],
+        )
+    }
+}
+
+const POWERS: [u8; 8] = *b"KMGTPEZY";
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", coreutils(0).to_string());
+        assert_eq!("512", coreutils(512).to_string());
+        assert_eq!("1K", coreutils(1024).to_string());
+        assert_eq!("1KB", coreutils(1000).to_string());
+        assert_eq!("1025", coreutils(1025).to_string());
+        assert_eq!("2000K", coreutils(2_048_000).to_string());
+        assert_eq!("4MB", coreutils(4_000_000).to_string());
+        assert_eq!("1536M", coreutils(1536 << 20).to_string());
+        assert_eq!("15E", coreutils(15 << 60).to_string());
+        assert_eq!(
+            CoreutilsSize::MAX_STRING_LEN,
+            coreutils(u64::MAX).to_string().len()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        // expected values are verified with `head -c <size> /dev/zero | wc -c` from coreutils 9.1
+        assert_eq!(coreutils(1024), "1K".parse().unwrap());
+        assert_eq!(coreutils(1024), "1k".parse().unwrap());
+        assert_eq!(coreutils(1024), "1KiB".parse().unwrap());
+        assert_eq!(coreutils(1024), "1kiB".parse().unwrap());
+        assert_eq!(coreutils(1024), "K".parse().unwrap());
+        assert_eq!(coreutils(1024), "KiB".parse().unwrap());
+        assert_eq!(coreutils(1000), "1KB".parse().unwrap());
+        assert_eq!(coreutils(1000), "1kD".parse().unwrap());
+        assert_eq!(coreutils(1000), "KB".parse().unwrap());
+        assert_eq!(coreutils(1 << 20), "1m".parse().unwrap());
+        assert_eq!(coreutils(1_000_000), "1MB".parse().unwrap());
+        assert_eq!(coreutils(1 << 40), "1T".parse().unwrap());
+        assert_eq!(coreutils(1 << 60), "1E".parse().unwrap());
+        assert_eq!(coreutils(1024), "2b".parse().unwrap());
+        assert_eq!(coreutils(512), "b".parse().unwrap());
+        assert_eq!(coreutils(10), "010".parse().unwrap());
+        assert_eq!(coreutils(1024), " 1K".parse().unwrap());
+        assert_eq!(coreutils(1024), "\t+1K".parse().unwrap());
+        assert_eq!(coreutils(0), "0Y".parse().unwrap());
+        assert_eq!(coreutils(15 << 60), "15E".parse().unwrap());
+        assert_eq!(coreutils(u64::MAX), "18446744073709551615".parse().unwrap());
+        for s in [
+            "",
+            " ",
+            "+",
+            "+K",
+            " K",
+            "-0",
+            "-1",
+            "1 K",
+            "1K ",
+            "1.5K",
+            "1Ki",
+            "1B",
+            "1BB",
+            "1bB",
+            "1c",
+            "1w",
+            "1g",
+            "1t",
+            "1Q",
+            "1e3",
+            "0x10",
+            "16E",
+            "1Z",
+            "1Y",
+            "18446744073709551616",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<CoreutilsSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<CoreutilsSize>(
+            &[
+                "0", "1", "9", "10", "b", "K", "k", "m", "M", "G", "E", "Z", "Y", "iB", "B", "D",
+                "i", " ", "\t", "+", ".",
+            ],
+            4,
+            |s| s.contains(['E', 'Z', 'Y']),
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: CoreutilsSize = u.arbitrary()?;
+            let string = expected.to_string();
+            let actual: CoreutilsSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn coreutils(size: u64) -> CoreutilsSize {
+        CoreutilsSize(Size(size))
+    }
+}
@@ -1,16 +1,25 @@
 use core::fmt::Display;
 use core::fmt::Formatter;
 use core::fmt::Write;
+use core::str::FromStr;
 
 use crate::Buffer;
 use crate::Size;
+use crate::SizeError;
 
 /**
 Approximate size that includes unit, integral and fractional parts as fields.
 
 This type is useful when you need custom formatting of the output,
 i.e. colors, locale-specific units etc.
+
+Parsing accepts the output of `Display` implementation (`1.5 KiB`).
+
+In human-readable serde formats the value is serialized as a string (`"1.5 KiB"`)
+and in binary formats as a struct;
+deserialization also accepts `{integer, fraction, unit}` map.
 */
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct FormattedSize {
     /// Size unit.
     pub unit: &'static str,
@@ -34,8 +43,100 @@ impl Display for FormattedSize {
     }
 }
 
+impl FromStr for FormattedSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (integer, fraction, unit) = parse_formatted(other, &UNITS).ok_or(SizeError)?;
+        Self::from_parts(integer, fraction, unit).ok_or(SizeError)
+    }
+}
+
+impl FormattedSize {
+    fn from_parts(integer: u64, fraction: u8, unit: &'static str) -> Option<Self> {
+        if integer > MAX_INTEGER as u64 || fraction > 9 {
+            return None;
+        }
+        Some(Self {
+            unit,
+            integer: integer as u16,
+            fraction,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::FormattedParts for FormattedSize {
+    const NAME: &'static str = "FormattedSize";
+    const UNITS: &'static [&'static str] = &UNITS;
+
+    fn from_parts(integer: u64, fraction: u8, unit: &'static str) -> Option<Self> {
+        Self::from_parts(integer, fraction, unit)
+    }
+
+    fn to_parts(&self) -> (u64, u8, &'static str) {
+        (self.integer as u64, self.fraction, self.unit)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FormattedSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_formatted::<MAX_LEN, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for FormattedSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_formatted(d)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for FormattedSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "FormattedSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::FormattedSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::formatted_schema(
+            "Approximate size with unit B, KiB, MiB, GiB, TiB, PiB or EiB",
+            r"^[0-9]+(\.[1-9])? (B|KiB|MiB|GiB|TiB|PiB|EiB)$",
+            MAX_INTEGER as u64,
+            &UNITS,
+            &["1.5 KiB", "512 MiB"],
+        )
+    }
+}
+
+/// Parses `<integer>[.<fraction>] <unit>` string.
+pub(crate) fn parse_formatted(s: &str, units: &[&'static str]) -> Option<(u64, u8, &'static str)> {
+    let (number, unit) = s.split_once(' ')?;
+    let unit = units.iter().find(|u| **u == unit)?;
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => match fraction.as_bytes() {
+            [digit @ b'1'..=b'9'] => (integer, digit - b'0'),
+            _ => return None,
+        },
+        None => (number, 0),
+    };
+    let integer = crate::parse_decimal(integer)?;
+    Some((integer, fraction, unit))
+}
+
 const MAX_LEN: usize = 10;
 const MAX_POWOF10: u64 = 1000;
+const MAX_INTEGER: u16 = 1023;
 
 /**
 This trait adds [`format_size`](FormatSize::format_size) method
@@ -83,15 +184,146 @@ impl FormatSize for Size {
 
 const UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
 
+/**
+This trait adds [`format_size_numfmt`](FormatSizeNumfmt::format_size_numfmt) method
+to primitive [`u64`](core::u64) and [`usize`](core::u64) types.
+*/
+pub trait FormatSizeNumfmt {
+    /// Formats the size the same way as GNU `numfmt --to=<style>` does.
+    fn format_size_numfmt(self, style: NumfmtStyle) -> NumfmtSize;
+}
+
+impl FormatSizeNumfmt for u64 {
+    fn format_size_numfmt(self, style: NumfmtStyle) -> NumfmtSize {
+        format_numfmt(self, style)
+    }
+}
+
+impl FormatSizeNumfmt for usize {
+    fn format_size_numfmt(self, style: NumfmtStyle) -> NumfmtSize {
+        format_numfmt(self as u64, style)
+    }
+}
+
+impl FormatSizeNumfmt for Size {
+    fn format_size_numfmt(self, style: NumfmtStyle) -> NumfmtSize {
+        format_numfmt(self.0, style)
+    }
+}
+
+/**
+Output style of GNU `numfmt --to=<style>`.
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+pub enum NumfmtStyle {
+    /// `--to=si`: powers of 1000 with `K`, `M`, `G`, ... suffixes.
+    Si,
+    /// `--to=iec`: powers of 1024 with `K`, `M`, `G`, ... suffixes.
+    Iec,
+    /// `--to=iec-i`: powers of 1024 with `Ki`, `Mi`, `Gi`, ... suffixes.
+    IecI,
+}
+
+/**
+Approximate size in GNU `numfmt` format (`1.5K`, `977Ki`, `19E`).
+
+The value is rounded away from zero as with the default `--round=from-zero` option,
+and the fractional part is present only for values less than 10.
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NumfmtSize {
+    /// Size unit. Empty for sizes less than 1000 or 1024.
+    pub unit: &'static str,
+    /// Integral part. Max. value is 1023.
+    pub integer: u16,
+    /// Fractional part. Max. value is 9.
+    pub fraction: Option<u8>,
+}
+
+impl Display for NumfmtSize {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.integer)?;
+        if let Some(fraction) = self.fraction {
+            write!(f, ".{}", fraction)?;
+        }
+        f.write_str(self.unit)
+    }
+}
+
+fn format_numfmt(size: u64, style: NumfmtStyle) -> NumfmtSize {
+    let (base, units) = match style {
+        NumfmtStyle::Si => (1000, &NUMFMT_UNITS),
+        NumfmtStyle::Iec => (1024, &NUMFMT_UNITS),
+        NumfmtStyle::IecI => (1024, &NUMFMT_IEC_I_UNITS),
+    };
+    let size = size as u128;
+    let mut power = 0;
+    let mut scale = 1;
+    while size / scale >= base {
+        scale *= base;
+        power += 1;
+    }
+    if power == 0 {
+        return NumfmtSize {
+            unit: "",
+            integer: size as u16,
+            fraction: None,
+        };
+    }
+    // round away from zero keeping one decimal digit for values less than 10
+    let mut tenths = if size < 10 * scale {
+        (size * 10 + scale - 1) / scale
+    } else {
+        (size + scale - 1) / scale * 10
+    };
+    if tenths >= base * 10 {
+        tenths /= base;
+        power += 1;
+    }
+    NumfmtSize {
+        unit: units[power],
+        integer: (tenths / 10) as u16,
+        fraction: if tenths < 100 {
+            Some((tenths % 10) as u8)
+        } else {
+            None
+        },
+    }
+}
+
+const NUMFMT_UNITS: [&str; 7] = ["", "K", "M", "G", "T", "P", "E"];
+const NUMFMT_IEC_I_UNITS: [&str; 7] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a> arbitrary::Arbitrary<'a> for FormattedSize {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            unit: *u.choose(&UNITS[..])?,
+            integer: u.int_in_range(0..=MAX_INTEGER)?,
+            fraction: u.int_in_range(0..=9)?,
+        })
+    }
+}
+
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a> arbitrary::Arbitrary<'a> for NumfmtSize {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(format_numfmt(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
 #[cfg(all(test, not(feature = "no_std")))]
 mod tests {
     #![allow(clippy::panic)]
-    use arbitrary::Arbitrary;
-    use arbitrary::Unstructured;
     use arbtest::arbtest;
 
     use super::*;
     use crate::FormatSize;
+    use crate::FormatSizeNumfmt;
 
     #[test]
     fn test_format_bytes() {
@@ -178,16 +410,180 @@ mod tests {
         });
     }
 
-    impl<'a> Arbitrary<'a> for FormattedSize {
-        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self, arbitrary::Error> {
-            Ok(Self {
-                unit: *u.choose(&UNITS[..])?,
-                integer: u.int_in_range(0..=MAX_INTEGER)?,
-                fraction: u.int_in_range(0..=9)?,
-            })
+    #[test]
+    fn test_parse_formatted() {
+        assert_eq!(
+            FormattedSize {
+                unit: "KiB",
+                integer: 1,
+                fraction: 5
+            },
+            "1.5 KiB".parse().unwrap()
+        );
+        assert_eq!(
+            FormattedSize {
+                unit: "B",
+                integer: 0,
+                fraction: 0
+            },
+            "0 B".parse().unwrap()
+        );
+        assert_eq!(
+            FormattedSize {
+                unit: "EiB",
+                integer: 1023,
+                fraction: 9
+            },
+            "1023.9 EiB".parse().unwrap()
+        );
+        for s in [
+            "", "1", "1 ", " B", "1B", "1  B", "1 kib", "1.0 KiB", "1.10 KiB", "1. KiB", ".5 KiB",
+            "-1 B", "1024 B", "1 KiB ",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<FormattedSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+        arbtest(|u| {
+            let expected: FormattedSize = u.arbitrary()?;
+            let actual: FormattedSize = expected.to_string().parse().unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_formatted() {
+        let expected = FormattedSize {
+            unit: "KiB",
+            integer: 1,
+            fraction: 5,
+        };
+        let string = serde_json::to_string(&expected).unwrap();
+        assert_eq!(
+            serde_json::to_string(&expected.to_string()).unwrap(),
+            string
+        );
+        assert_eq!(expected, serde_json::from_str(&string).unwrap());
+        assert_eq!(
+            expected,
+            serde_json::from_str(r#"{"integer":1,"fraction":5,"unit":"KiB"}"#).unwrap()
+        );
+        for s in [
+            r#"{"integer":1,"fraction":5}"#,
+            r#"{"integer":1,"fraction":10,"unit":"KiB"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"x"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"KiB","x":1}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<FormattedSize>(s).is_err(),
+                "s = `{}`",
+                s
+            );
+        }
+        arbtest(|u| {
+            let expected: FormattedSize = u.arbitrary()?;
+            let bytes = bincode::serialize(&expected).unwrap();
+            let actual = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            let string = serde_json::to_string(&expected).unwrap();
+            let actual = serde_json::from_str(&string).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[cfg(all(feature = "serde", feature = "schemars"))]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<FormattedSize>(
+            &[
+                "0", "1", "9", "10", "B", "KiB", "MiB", "EiB", "kB", " ", "+", ".",
+            ],
+            3,
+            |_| false,
+        );
+        let schema = serde_json::to_value(schemars::schema_for!(FormattedSize)).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for s in [
+            r#"{"integer":1,"fraction":5,"unit":"KiB"}"#,
+            r#"{"integer":1,"fraction":5}"#,
+            r#"{"integer":1,"fraction":10,"unit":"KiB"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"x"}"#,
+            r#"{"integer":1,"fraction":5,"unit":"KiB","x":1}"#,
+        ] {
+            assert_eq!(
+                serde_json::from_str::<FormattedSize>(s).is_ok(),
+                validator.is_valid(&serde_json::from_str(s).unwrap()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_numfmt() {
+        // expected values are the output of `numfmt --to=<style>` from coreutils 9.1
+        let sizes = [
+            0,
+            1,
+            512,
+            999,
+            1000,
+            1001,
+            1023,
+            1024,
+            1025,
+            1536,
+            9950,
+            9951,
+            10239,
+            10240,
+            10241,
+            999500,
+            999999,
+            1048575,
+            1048576,
+            u64::MAX,
+        ];
+        let expected = [
+            (
+                NumfmtStyle::Iec,
+                "0 1 512 999 1000 1001 1023 1.0K 1.1K 1.5K 9.8K 9.8K 10K 10K 11K 977K 977K 1.0M 1.0M 16E",
+            ),
+            (
+                NumfmtStyle::Si,
+                "0 1 512 999 1.0K 1.1K 1.1K 1.1K 1.1K 1.6K 10K 10K 11K 11K 11K 1.0M 1.0M 1.1M 1.1M 19E",
+            ),
+            (
+                NumfmtStyle::IecI,
+                "0 1 512 999 1000 1001 1023 1.0Ki 1.1Ki 1.5Ki 9.8Ki 9.8Ki 10Ki 10Ki 11Ki 977Ki 977Ki 1.0Mi 1.0Mi 16Ei",
+            ),
+        ];
+        for (style, expected) in expected {
+            let actual = sizes
+                .iter()
+                .map(|size| size.format_size_numfmt(style).to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            assert_eq!(expected, actual, "style = {:?}", style);
         }
     }
 
+    #[test]
+    fn test_numfmt_arbitrary() {
+        arbtest(|u| {
+            let size: NumfmtSize = u.arbitrary()?;
+            assert!(size.integer <= MAX_INTEGER);
+            assert!(size.fraction.map(|_| size.integer < 10).unwrap_or(true));
+            Ok(())
+        });
+    }
+
     fn unit_to_factor(unit: &str) -> u64 {
         match unit {
             "B" => 1_u64,
@@ -200,6 +596,4 @@ mod tests {
             _ => panic!("unknown unit `{}`", unit),
         }
     }
-
-    const MAX_INTEGER: u16 = 1023;
 }
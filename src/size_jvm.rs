@@ -0,0 +1,232 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in JVM memory flag format (`-Xmx512m`, `-Xss1024k`, `-XX:MaxDirectMemorySize=4g`).
+
+Parsing follows HotSpot's `atojulong`:
+the number is decimal or hexadecimal (`0x400`)
+followed by an optional single-letter suffix `k`, `m`, `g` or `t` in either case,
+suffixes are powers of 1024, spaces, fractions and IEC suffixes such as `KiB` are not allowed.
+
+Formatting uses the largest lower-case suffix that divides the size exactly.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct JvmSize(pub Size);
+
+impl JvmSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for JvmSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut size = self.0 .0;
+        let mut unit = "";
+        if size != 0 {
+            for u in UNITS {
+                if size % 1024 != 0 {
+                    break;
+                }
+                size /= 1024;
+                unit = u;
+            }
+        }
+        write!(f, "{}{}", size, unit)
+    }
+}
+
+impl FromStr for JvmSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (digits, radix) = match other
+            .strip_prefix("0x")
+            .or_else(|| other.strip_prefix("0X"))
+        {
+            Some(hex) => (hex, 16),
+            None => (other, 10),
+        };
+        let len = digits
+            .find(|ch: char| !ch.is_digit(radix))
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return Err(SizeError);
+        }
+        let size = u64::from_str_radix(&digits[..len], radix).map_err(|_| SizeError)?;
+        let shift = match &digits[len..] {
+            "" => 0,
+            "k" | "K" => 10,
+            "m" | "M" => 20,
+            "g" | "G" => 30,
+            "t" | "T" => 40,
+            _ => return Err(SizeError),
+        };
+        if size.leading_zeros() < shift {
+            return Err(SizeError);
+        }
+        Ok(Self(Size(size << shift)))
+    }
+}
+
+impl From<Size> for JvmSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<JvmSize> for Size {
+    fn from(other: JvmSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for JvmSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for JvmSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JvmSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ JvmSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for JvmSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a JVM memory size")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for JvmSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "JvmSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::JvmSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in JVM memory flag format: decimal or hexadecimal number with optional unit k, m, g or t (powers of 1024)",
+            r"^(0[xX][0-9a-fA-F]+|[0-9]+)[kKmMgGtT]?$",
+            &["512m", "1024k", "0x400"],
+        )
+    }
+}
+
+const UNITS: [&str; 4] = ["k", "m", "g", "t"];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", jvm(0).to_string());
+        assert_eq!("1023", jvm(1023).to_string());
+        assert_eq!("1k", jvm(1024).to_string());
+        assert_eq!("1025", jvm(1025).to_string());
+        assert_eq!("512m", jvm(512 * 1024 * 1024).to_string());
+        assert_eq!("4g", jvm(4 * 1024 * 1024 * 1024).to_string());
+        assert_eq!("1536m", jvm(1536 * 1024 * 1024).to_string());
+        assert_eq!("1024t", jvm(1 << 50).to_string());
+        assert_eq!(JvmSize::MAX_STRING_LEN, jvm(u64::MAX).to_string().len());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(jvm(512 * 1024 * 1024), "512m".parse().unwrap());
+        assert_eq!(jvm(512 * 1024 * 1024), "512M".parse().unwrap());
+        assert_eq!(jvm(4 * 1024 * 1024 * 1024), "4g".parse().unwrap());
+        assert_eq!(jvm(1024 * 1024), "1024k".parse().unwrap());
+        assert_eq!(jvm(1 << 40), "1T".parse().unwrap());
+        assert_eq!(jvm(1024), "1024".parse().unwrap());
+        assert_eq!(jvm(1024 * 1024), "0x400k".parse().unwrap());
+        assert_eq!(jvm(u64::MAX), "18446744073709551615".parse().unwrap());
+        assert_eq!(jvm(16777215 << 40), "16777215t".parse().unwrap());
+        for s in [
+            "",
+            "k",
+            "0x",
+            "1.5g",
+            "512 m",
+            " 512m",
+            "512m ",
+            "512mb",
+            "512MiB",
+            "512KiB",
+            "+1k",
+            "-1k",
+            "1kk",
+            "16777216t",
+            "18446744073709551616",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<JvmSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<JvmSize>(
+            &[
+                "0", "1", "9", "10", "0x", "0X", "a", "F", "k", "K", "m", "G", "t", "KiB", " ",
+                "+", ".",
+            ],
+            6,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: JvmSize = u.arbitrary()?;
+            let string = expected.to_string();
+            let actual: JvmSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn jvm(size: u64) -> JvmSize {
+        JvmSize(Size(size))
+    }
+}
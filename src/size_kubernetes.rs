@@ -0,0 +1,369 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in [Kubernetes quantity](https://kubernetes.io/docs/reference/kubernetes-api/common-definitions/quantity/)
+format.
+
+Parsing accepts binary SI suffixes (`Ki`, `Mi`, `Gi`, `Ti`, `Pi`, `Ei`),
+decimal SI suffixes (`m`, `k`, `M`, `G`, `T`, `P`, `E`),
+decimal exponents (`129e6`), fractions (`1.5Gi`) and the optional `+` sign.
+Quantities that are negative or that are not an integral number of bytes are rejected.
+
+Formatting produces an equivalent quantity, binary SI suffix when exact:
+binary SI is used for non-zero multiples of 1024 and decimal SI otherwise,
+the suffix is the largest one that does not produce a fraction.
+Unlike Kubernetes the original format is not preserved,
+e.g. `1024` is formatted as `1Ki` and `129e6` as `129M`.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct KubernetesSize(pub Size);
+
+impl KubernetesSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for KubernetesSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut size = self.0 .0;
+        let (units, base) = if size != 0 && size % 1024 == 0 {
+            (BINARY_UNITS, 1024)
+        } else {
+            (DECIMAL_UNITS, 1000)
+        };
+        let mut unit = "";
+        if size != 0 {
+            for u in units {
+                if size % base != 0 {
+                    break;
+                }
+                size /= base;
+                unit = u;
+            }
+        }
+        write!(f, "{}{}", size, unit)
+    }
+}
+
+impl FromStr for KubernetesSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let other = other.strip_prefix('+').unwrap_or(other);
+        let number_len = other
+            .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+            .unwrap_or(other.len());
+        let (number, suffix) = other.split_at(number_len);
+        let (integer, fraction) = match number.find('.') {
+            Some(i) => (&number[..i], &number[(i + 1)..]),
+            None => (number, ""),
+        };
+        if integer.is_empty() && fraction.is_empty() || fraction.contains('.') {
+            return Err(SizeError);
+        }
+        let mut size: u128 = 0;
+        for digit in integer.bytes().chain(fraction.bytes()) {
+            size = size
+                .checked_mul(10)
+                .and_then(|size| size.checked_add((digit - b'0') as u128))
+                .ok_or(SizeError)?;
+        }
+        let mut exponent = -(fraction.len() as i64);
+        match suffix {
+            "" => {}
+            "Ki" | "Mi" | "Gi" | "Ti" | "Pi" | "Ei" => {
+                let i = BINARY_UNITS
+                    .iter()
+                    .position(|unit| *unit == suffix)
+                    .ok_or(SizeError)?;
+                size = size
+                    .checked_mul(1024_u128.pow(i as u32 + 1))
+                    .ok_or(SizeError)?;
+            }
+            "m" => exponent -= 3,
+            "k" | "M" | "G" | "T" | "P" | "E" => {
+                let i = DECIMAL_UNITS
+                    .iter()
+                    .position(|unit| *unit == suffix)
+                    .ok_or(SizeError)?;
+                exponent += 3 * (i as i64 + 1);
+            }
+            _ => {
+                let e = suffix
+                    .strip_prefix('e')
+                    .or_else(|| suffix.strip_prefix('E'))
+                    .ok_or(SizeError)?;
+                let digits = e.strip_prefix(['+', '-']).unwrap_or(e);
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(SizeError);
+                }
+                exponent += e.parse::<i32>().map_err(|_| SizeError)? as i64;
+            }
+        }
+        if size != 0 {
+            let factor = 10_u128
+                .checked_pow(exponent.unsigned_abs().try_into().map_err(|_| SizeError)?)
+                .ok_or(SizeError)?;
+            if exponent >= 0 {
+                size = size.checked_mul(factor).ok_or(SizeError)?;
+            } else {
+                if size % factor != 0 {
+                    return Err(SizeError);
+                }
+                size /= factor;
+            }
+        }
+        let size: u64 = size.try_into().map_err(|_| SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl From<Size> for KubernetesSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<KubernetesSize> for Size {
+    fn from(other: KubernetesSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for KubernetesSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for KubernetesSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KubernetesSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ KubernetesSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for KubernetesSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a Kubernetes quantity")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for KubernetesSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "KubernetesSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::KubernetesSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in Kubernetes quantity format with optional binary (Ki, Mi, Gi, Ti, Pi, Ei) or decimal (m, k, M, G, T, P, E) suffix or decimal exponent; the quantity must be a whole number of bytes",
+            r"^\+?([0-9]+(\.[0-9]*)?|\.[0-9]+)([mkMGTPE]|[KMGTPE]i|[eE][+-]?[0-9]+)?$",
+            &["128Mi", "1.5Gi", "1G", "129e6"],
+        )
+    }
+}
+
+const BINARY_UNITS: [&str; 6] = ["Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+const DECIMAL_UNITS: [&str; 6] = ["k", "M", "G", "T", "P", "E"];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", kubernetes(0).to_string());
+        assert_eq!("512", kubernetes(512).to_string());
+        assert_eq!("1Ki", kubernetes(1024).to_string());
+        assert_eq!("1025", kubernetes(1025).to_string());
+        assert_eq!("1536Mi", kubernetes(1536 * 1024 * 1024).to_string());
+        assert_eq!("1G", kubernetes(1000 * 1000 * 1000).to_string());
+        assert_eq!("129M", kubernetes(129000000).to_string());
+        assert_eq!("123Mi", kubernetes(128974848).to_string());
+        assert_eq!("128974849", kubernetes(128974849).to_string());
+        assert_eq!("1500", kubernetes(1500).to_string());
+        assert_eq!("15Ei", kubernetes(15 << 60).to_string());
+        assert_eq!("18446744073709551615", kubernetes(u64::MAX).to_string());
+    }
+
+    #[test]
+    fn test_display_does_not_preserve_format() {
+        // Kubernetes would print the same strings that were parsed
+        for (s, expected) in [("1024", "1Ki"), ("129e6", "129M"), ("1000k", "1M")] {
+            assert_eq!(
+                expected,
+                s.parse::<KubernetesSize>().unwrap().to_string(),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(kubernetes(512 * 1024 * 1024), "512Mi".parse().unwrap());
+        assert_eq!(kubernetes(1024 * 1024 * 1024), "1Gi".parse().unwrap());
+        assert_eq!(kubernetes(1000 * 1000 * 1000), "1G".parse().unwrap());
+        assert_eq!(kubernetes(128974848), "128974848".parse().unwrap());
+        assert_eq!(kubernetes(129000000), "129e6".parse().unwrap());
+        assert_eq!(kubernetes(129000000), "129E6".parse().unwrap());
+        assert_eq!(kubernetes(129000000), "129e+6".parse().unwrap());
+        assert_eq!(kubernetes(129), "129000e-3".parse().unwrap());
+        assert_eq!(kubernetes(1536 * 1024 * 1024), "1.5Gi".parse().unwrap());
+        assert_eq!(kubernetes(500), ".5k".parse().unwrap());
+        assert_eq!(kubernetes(5000), "5.k".parse().unwrap());
+        assert_eq!(kubernetes(1), "1000m".parse().unwrap());
+        assert_eq!(kubernetes(1), "+1".parse().unwrap());
+        assert_eq!(kubernetes(0), "0e100".parse().unwrap());
+        assert_eq!(kubernetes(10_u64.pow(18)), "1E".parse().unwrap());
+        assert_eq!(
+            kubernetes(u64::MAX),
+            "18446744073709551615".parse().unwrap()
+        );
+        for s in [
+            "",
+            ".",
+            "-1",
+            "1.5",
+            "1500m",
+            "1 Gi",
+            " 1Gi",
+            "1Gi ",
+            "1gi",
+            "1K",
+            "1KiB",
+            "1e",
+            "1e+",
+            "1.2.3",
+            "16Ei",
+            "18446744073709551616",
+            "1e20",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<KubernetesSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<KubernetesSize>(
+            &[
+                "0", "1", "9", "10", "k", "K", "i", "Mi", "m", "E", "e", "e3", "-", " ", "+", ".",
+            ],
+            3,
+            |_| true,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: KubernetesSize = u.arbitrary()?;
+            let string = expected.to_string();
+            assert!(string.len() <= KubernetesSize::MAX_STRING_LEN);
+            let actual: KubernetesSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn parse_display_symmetry() {
+        arbtest(|u| {
+            let (unit, max) = *u
+                .choose(&[
+                    ("", u64::MAX),
+                    ("Ki", u64::MAX >> 10),
+                    ("Mi", u64::MAX >> 20),
+                    ("Gi", u64::MAX >> 30),
+                    ("Ti", u64::MAX >> 40),
+                    ("Pi", u64::MAX >> 50),
+                    ("Ei", u64::MAX >> 60),
+                    ("k", u64::MAX / 10_u64.pow(3)),
+                    ("M", u64::MAX / 10_u64.pow(6)),
+                    ("G", u64::MAX / 10_u64.pow(9)),
+                    ("T", u64::MAX / 10_u64.pow(12)),
+                    ("P", u64::MAX / 10_u64.pow(15)),
+                    ("E", u64::MAX / 10_u64.pow(18)),
+                    ("e2", u64::MAX / 10_u64.pow(2)),
+                ])
+                .unwrap();
+            let number: u64 = u.int_in_range(0_u64..=max)?;
+            let expected = format!("{}{}", number, unit);
+            let expected_size: KubernetesSize = expected.parse().unwrap();
+            let actual = expected_size.to_string();
+            let actual_size: KubernetesSize = actual.parse().unwrap();
+            assert_eq!(
+                expected_size, actual_size,
+                "string 1 = `{}`, string 2 = `{}`",
+                expected, actual
+            );
+            Ok(())
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_io() {
+        assert_eq!(
+            "\"512Mi\"",
+            serde_json::to_string(&kubernetes(512 * 1024 * 1024)).unwrap()
+        );
+        assert_eq!(
+            kubernetes(129000000),
+            serde_json::from_str("\"129e6\"").unwrap()
+        );
+        assert_eq!(
+            kubernetes(128974848),
+            serde_yaml::from_str("128974848").unwrap()
+        );
+        assert_eq!(
+            "invalid value: string \"512MB\", expected a Kubernetes quantity at line 1 column 7",
+            serde_json::from_str::<KubernetesSize>("\"512MB\"")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    fn kubernetes(size: u64) -> KubernetesSize {
+        KubernetesSize(Size(size))
+    }
+}
@@ -0,0 +1,220 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in [nginx configuration](https://nginx.org/en/docs/syntax.html) format
+(`client_max_body_size 8m;`).
+
+Parsing follows nginx's `ngx_parse_size`:
+the number is followed by an optional suffix `k`, `m` or `g` in either case,
+suffixes are powers of 1024, spaces, signs and fractions are not allowed,
+and sizes larger than `i64::MAX` are rejected.
+
+Formatting uses the largest lower-case suffix that divides the size exactly.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct NginxSize(pub Size);
+
+impl NginxSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 20;
+}
+
+impl Display for NginxSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut size = self.0 .0;
+        let mut unit = "";
+        if size != 0 {
+            for u in UNITS {
+                if size % 1024 != 0 {
+                    break;
+                }
+                size /= 1024;
+                unit = u;
+            }
+        }
+        write!(f, "{}{}", size, unit)
+    }
+}
+
+impl FromStr for NginxSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let (number, shift) = match other.as_bytes().last() {
+            Some(b'k' | b'K') => (&other[..(other.len() - 1)], 10),
+            Some(b'm' | b'M') => (&other[..(other.len() - 1)], 20),
+            Some(b'g' | b'G') => (&other[..(other.len() - 1)], 30),
+            _ => (other, 0),
+        };
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(SizeError);
+        }
+        let size: u64 = number.parse().map_err(|_| SizeError)?;
+        if size > (i64::MAX as u64) >> shift {
+            return Err(SizeError);
+        }
+        Ok(Self(Size(size << shift)))
+    }
+}
+
+impl From<Size> for NginxSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<NginxSize> for Size {
+    fn from(other: NginxSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for NginxSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for NginxSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NginxSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ NginxSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for NginxSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "an nginx size")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for NginxSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "NginxSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::NginxSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in nginx configuration format with optional unit k, m or g (powers of 1024)",
+            r"^[0-9]+[kKmMgG]?$",
+            &["512", "8k", "1m"],
+        )
+    }
+}
+
+const UNITS: [&str; 3] = ["k", "m", "g"];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", nginx(0).to_string());
+        assert_eq!("1025", nginx(1025).to_string());
+        assert_eq!("8k", nginx(8 * 1024).to_string());
+        assert_eq!("1m", nginx(1024 * 1024).to_string());
+        assert_eq!("1g", nginx(1024 * 1024 * 1024).to_string());
+        assert_eq!("1024g", nginx(1 << 40).to_string());
+        assert_eq!(NginxSize::MAX_STRING_LEN, nginx(u64::MAX).to_string().len());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(nginx(8 * 1024), "8k".parse().unwrap());
+        assert_eq!(nginx(8 * 1024), "8K".parse().unwrap());
+        assert_eq!(nginx(1024 * 1024), "1m".parse().unwrap());
+        assert_eq!(nginx(1024 * 1024 * 1024), "1G".parse().unwrap());
+        assert_eq!(nginx(1025), "1025".parse().unwrap());
+        assert_eq!(
+            nginx(i64::MAX as u64),
+            "9223372036854775807".parse().unwrap()
+        );
+        assert_eq!(
+            nginx((i64::MAX as u64) >> 30 << 30),
+            "8589934591g".parse().unwrap()
+        );
+        for s in [
+            "",
+            "k",
+            "1t",
+            "1kb",
+            "1 k",
+            " 1k",
+            "1k ",
+            "+1k",
+            "-1k",
+            "1.5k",
+            "1kk",
+            "8589934592g",
+            "9223372036854775808",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<NginxSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<NginxSize>(
+            &[
+                "0", "1", "9", "10", "k", "K", "m", "M", "g", "G", "t", " ", "+", ".", "-",
+            ],
+            9,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected = nginx(u.int_in_range(0..=i64::MAX as u64)?);
+            let string = expected.to_string();
+            let actual: NginxSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn nginx(size: u64) -> NginxSize {
+        NginxSize(Size(size))
+    }
+}
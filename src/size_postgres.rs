@@ -0,0 +1,393 @@
+use core::fmt::Display;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Exact size in [PostgreSQL configuration](https://www.postgresql.org/docs/current/config-setting.html#CONFIG-SETTING-NAMES-VALUES)
+format.
+
+Parsing follows PostgreSQL's `parse_int`:
+units `B`, `kB`, `MB`, `GB` and `TB` are powers of 1024 and are case-sensitive,
+whitespace is allowed around the number and the unit,
+integers may be written in octal (`010`) or hexadecimal (`0x10`) form,
+decimal fractions and exponents (`1.5GB`, `1e3kB`) are rounded to the nearest byte,
+and the value without a unit is the number of kilobytes
+(the base unit of most PostgreSQL memory settings, e.g. `work_mem`).
+Use [`parse_with_base`](PostgresSize::parse_with_base) for the settings with other base units,
+e.g. `shared_buffers` that is measured in 8 kB blocks.
+
+The value `-1` that disables some settings (e.g. `temp_file_limit`) is rejected;
+use [`Limit<PostgresSize>`](crate::Limit) to parse it as no limit.
+
+Formatting produces the same output as PostgreSQL's `SHOW` command:
+the largest unit that divides the size exactly, no space between the number and the unit,
+and zero without a unit.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct PostgresSize(pub Size);
+
+impl PostgresSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 21;
+
+    /// Parses the setting using `base` as the unit of the value without a unit.
+    pub fn parse_with_base(s: &str, base: Size) -> Result<Self, SizeError> {
+        let size = parse_guc(s, &UNITS, base.0 as u128).ok_or(SizeError)?;
+        let size: u64 = size.try_into().map_err(|_| SizeError)?;
+        Ok(Self(Size(size)))
+    }
+}
+
+impl Display for PostgresSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let size = self.0 .0;
+        if size == 0 {
+            return f.write_str("0");
+        }
+        for (unit, factor) in UNITS {
+            let factor = factor as u64;
+            if size % factor == 0 {
+                return write!(f, "{}{}", size / factor, unit);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PostgresSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_base(other, Size(KILOBYTE))
+    }
+}
+
+impl From<Size> for PostgresSize {
+    fn from(other: Size) -> Self {
+        Self(other)
+    }
+}
+
+impl From<PostgresSize> for Size {
+    fn from(other: PostgresSize) -> Self {
+        other.0
+    }
+}
+
+impl Deref for PostgresSize {
+    type Target = Size;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PostgresSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PostgresSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ PostgresSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for PostgresSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a PostgreSQL memory setting")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for PostgresSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "PostgresSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::PostgresSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size in PostgreSQL configuration format with optional unit B, kB, MB, GB or TB (powers of 1024); the number without a unit is the number of kilobytes",
+            r"^[ \t\n\x0B\x0C\r]*[+-]?(0[xX][0-9a-fA-F]+|(0[0-7]*|[1-9][0-9]*)(\.[0-9]*)?([eE][+-]?[0-9]+)?|\.[0-9]+([eE][+-]?[0-9]+)?)([ \t\n\x0B\x0C\r]*(B|kB|MB|GB|TB))?[ \t\n\x0B\x0C\r]*$",
+            &["128MB", "1GB", "8kB"],
+        )
+    }
+}
+
+/**
+Parses PostgreSQL integer setting with an optional unit.
+
+Returns the value in base units (i.e. the units with factor 1) rounded to the nearest integer.
+The value without a unit is multiplied by `default_factor`.
+*/
+pub(crate) fn parse_guc(value: &str, units: &[(&str, u128)], default_factor: u128) -> Option<u128> {
+    let value = value.trim_start_matches(is_space);
+    let (negative, value) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (numerator, scale, rest) = parse_number(value)?;
+    let rest = rest.trim_start_matches(is_space);
+    let factor = if rest.is_empty() {
+        default_factor
+    } else {
+        let unit_len = rest.find(is_space).unwrap_or(rest.len());
+        let (unit, rest) = rest.split_at(unit_len);
+        if !rest.trim_start_matches(is_space).is_empty() {
+            return None;
+        }
+        units.iter().find(|(u, _)| *u == unit)?.1
+    };
+    let value = round_half_even(numerator.checked_mul(factor)?, scale)?;
+    if negative && value != 0 {
+        return None;
+    }
+    Some(value)
+}
+
+/// Parses the number the same way as `strtol` with base 0 followed by `strtod` does.
+///
+/// Returns the numerator, the no. of decimal places and the remaining string.
+fn parse_number(s: &str) -> Option<(u128, i32, &str)> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        let len = hex
+            .find(|ch: char| !ch.is_ascii_hexdigit())
+            .unwrap_or(hex.len());
+        if len != 0 {
+            if hex[len..].starts_with('.') {
+                // hexadecimal floating point numbers are not supported
+                return None;
+            }
+            let n = u128::from_str_radix(&hex[..len], 16).ok()?;
+            return Some((n, 0, &hex[len..]));
+        }
+    }
+    let radix = if s.starts_with('0') { 8 } else { 10 };
+    let len = s.find(|ch: char| !ch.is_digit(radix)).unwrap_or(s.len());
+    if !s[len..].starts_with(['.', 'e', 'E']) {
+        if len == 0 {
+            return None;
+        }
+        let n = u128::from_str_radix(&s[..len], radix).ok()?;
+        return Some((n, 0, &s[len..]));
+    }
+    // decimal floating point number
+    let integer_len = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+    let (integer, mut rest) = s.split_at(integer_len);
+    let mut fraction = "";
+    if let Some(s) = rest.strip_prefix('.') {
+        let fraction_len = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+        fraction = &s[..fraction_len];
+        rest = &s[fraction_len..];
+    }
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let mut n: u128 = 0;
+    for digit in integer.bytes().chain(fraction.bytes()) {
+        n = n.checked_mul(10)?.checked_add((digit - b'0') as u128)?;
+    }
+    let mut scale = fraction.len() as i32;
+    if let Some(s) = rest.strip_prefix(['e', 'E']) {
+        let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+        let exponent_len = digits
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(digits.len());
+        if exponent_len != 0 {
+            let exponent_len = exponent_len + (s.len() - digits.len());
+            let exponent: i32 = s[..exponent_len].parse().ok()?;
+            scale = scale.checked_sub(exponent)?;
+            rest = &s[exponent_len..];
+        }
+    }
+    Some((n, scale, rest))
+}
+
+/// Computes `numerator / 10^scale` rounded half to even.
+fn round_half_even(numerator: u128, scale: i32) -> Option<u128> {
+    if scale <= 0 {
+        return numerator.checked_mul(10_u128.checked_pow(scale.unsigned_abs())?);
+    }
+    let denominator = match 10_u128.checked_pow(scale as u32) {
+        Some(d) => d,
+        // the fraction is less than 1/2
+        None => return Some(0),
+    };
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    let complement = denominator - remainder;
+    if remainder > complement || (remainder == complement && quotient % 2 == 1) {
+        Some(quotient + 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+pub(crate) const fn is_space(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\n' | '\x0b' | '\x0c' | '\r')
+}
+
+const KILOBYTE: u64 = 1024;
+
+const UNITS: [(&str, u128); 5] = [
+    ("TB", 1024 * 1024 * 1024 * 1024),
+    ("GB", 1024 * 1024 * 1024),
+    ("MB", 1024 * 1024),
+    ("kB", 1024),
+    ("B", 1),
+];
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use arbtest::arbtest;
+
+    use super::*;
+    use crate::Limit;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0", postgres(0).to_string());
+        assert_eq!("1B", postgres(1).to_string());
+        assert_eq!("1025B", postgres(1025).to_string());
+        assert_eq!("8kB", postgres(8 * 1024).to_string());
+        assert_eq!("128MB", postgres(128 * 1024 * 1024).to_string());
+        assert_eq!("1GB", postgres(1024 * 1024 * 1024).to_string());
+        assert_eq!("1TB", postgres(1024_u64.pow(4)).to_string());
+        assert_eq!("1024TB", postgres(1024_u64.pow(5)).to_string());
+        assert_eq!("1536MB", postgres(1536 * 1024 * 1024).to_string());
+        assert_eq!(
+            PostgresSize::MAX_STRING_LEN,
+            postgres(u64::MAX).to_string().len()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(postgres(8 * 1024), "8kB".parse().unwrap());
+        assert_eq!(postgres(128 * 1024 * 1024), "128MB".parse().unwrap());
+        assert_eq!(postgres(1024 * 1024 * 1024), "1GB".parse().unwrap());
+        assert_eq!(postgres(1024_u64.pow(4)), "1TB".parse().unwrap());
+        assert_eq!(postgres(4096 * 1024), "4096".parse().unwrap());
+        assert_eq!(postgres(1024), "1024B".parse().unwrap());
+        assert_eq!(postgres(1024), " 1 kB ".parse().unwrap());
+        assert_eq!(postgres(1024), "+1kB".parse().unwrap());
+        assert_eq!(postgres(1536 * 1024 * 1024), "1.5GB".parse().unwrap());
+        assert_eq!(postgres(1000 * 1024), "1e3kB".parse().unwrap());
+        assert_eq!(postgres(1126), "1.1kB".parse().unwrap());
+        assert_eq!(postgres(512), "0.5".parse().unwrap());
+        assert_eq!(postgres(1536), "1.5".parse().unwrap());
+        assert_eq!(postgres(0), "0.4B".parse().unwrap());
+        assert_eq!(postgres(2), "1.5B".parse().unwrap());
+        assert_eq!(postgres(2), "2.5B".parse().unwrap());
+        assert_eq!(postgres(8 * 1024), "010kB".parse().unwrap());
+        assert_eq!(postgres(16 * 1024), "0x10kB".parse().unwrap());
+        assert_eq!(postgres(10 * 1024), "010.0".parse().unwrap());
+        assert_eq!(postgres(0), "-0".parse().unwrap());
+        assert_eq!(postgres(0), "-0.4B".parse().unwrap());
+        assert_eq!(postgres(0), "0".parse().unwrap());
+        for s in [
+            "",
+            " ",
+            "kB",
+            "1kb",
+            "1KB",
+            "1mb",
+            "1 k B",
+            "1kB x",
+            "1PB",
+            "1MiB",
+            "08",
+            "09.5",
+            "-1",
+            "-1kB",
+            "0x1.8p1",
+            "1e",
+            ".",
+            "16777216TB",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<PostgresSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_with_base() {
+        let block = Size(8192);
+        assert_eq!(
+            postgres(128 << 20),
+            PostgresSize::parse_with_base("16384", block).unwrap()
+        );
+        assert_eq!(
+            postgres(128 << 20),
+            PostgresSize::parse_with_base("128MB", block).unwrap()
+        );
+        assert_eq!(
+            postgres(1024),
+            PostgresSize::parse_with_base("1024", Size(1)).unwrap()
+        );
+        assert!(PostgresSize::parse_with_base("-1", block).is_err());
+        assert_eq!(
+            Limit::Unlimited,
+            "-1".parse::<Limit<PostgresSize>>().unwrap()
+        );
+        assert_eq!(
+            Limit::Finite(postgres(1 << 30)),
+            "1GB".parse::<Limit<PostgresSize>>().unwrap()
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<PostgresSize>(
+            &[
+                "0", "1", "9", "10", "7", "8", "0x", "X", "a", "f", "e", "E", "B", "kB", "MB",
+                "TB", "kb", " ", "\t", "\x0b", "+", "-", ".",
+            ],
+            3,
+            |s| s.contains('-'),
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: PostgresSize = u.arbitrary()?;
+            let string = expected.to_string();
+            let actual: PostgresSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn postgres(size: u64) -> PostgresSize {
+        PostgresSize(Size(size))
+    }
+}
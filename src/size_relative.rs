@@ -0,0 +1,402 @@
+use core::cmp::Ordering;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::Size;
+use crate::SizeError;
+
+/**
+Size that is either absolute (`512m`) or relative to some total (`25%`, `12.5%`).
+
+Parsing accepts any string accepted by [`Size`] as well as [`Percentage`].
+Use [`resolve`](RelativeSize::resolve) to compute the size from the total
+(e.g. the amount of system memory or the capacity of the disk).
+*/
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+pub enum RelativeSize {
+    /// Exact size.
+    Absolute(Size),
+    /// Percentage of the total.
+    Relative(Percentage),
+}
+
+impl RelativeSize {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = Percentage::MAX_STRING_LEN;
+
+    /// Computes the size given the total.
+    ///
+    /// Returns `None` on overflow.
+    pub fn resolve(self, total: Size) -> Option<Size> {
+        match self {
+            Self::Absolute(size) => Some(size),
+            Self::Relative(percentage) => percentage.of(total),
+        }
+    }
+}
+
+impl Default for RelativeSize {
+    fn default() -> Self {
+        Self::Absolute(Size::default())
+    }
+}
+
+impl Display for RelativeSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Absolute(size) => size.fmt(f),
+            Self::Relative(percentage) => percentage.fmt(f),
+        }
+    }
+}
+
+impl FromStr for RelativeSize {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        if other.trim_end().ends_with('%') {
+            other.parse().map(Self::Relative)
+        } else {
+            other.parse().map(Self::Absolute)
+        }
+    }
+}
+
+impl From<Size> for RelativeSize {
+    fn from(other: Size) -> Self {
+        Self::Absolute(other)
+    }
+}
+
+impl From<Percentage> for RelativeSize {
+    fn from(other: Percentage) -> Self {
+        Self::Relative(other)
+    }
+}
+
+/**
+Exact percentage (`25%`, `12.5%`).
+
+The value is stored as a decimal number with up to [`MAX_SCALE`](Percentage::MAX_SCALE)
+digits after the decimal point; no floating point numbers are involved.
+Percentages greater than 100% are allowed.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Percentage {
+    // Percentage multiplied by `10^scale`.
+    mantissa: u64,
+    // The number of digits after the decimal point without trailing zeroes.
+    scale: u8,
+}
+
+impl Percentage {
+    /// Max. length in string form.
+    pub const MAX_STRING_LEN: usize = 22;
+
+    /// Max. number of digits after the decimal point.
+    pub const MAX_SCALE: u32 = 19;
+
+    /// Creates integer percentage.
+    pub const fn new(percent: u64) -> Self {
+        Self {
+            mantissa: percent,
+            scale: 0,
+        }
+    }
+
+    /// Creates percentage equal to `mantissa / 10^scale`.
+    ///
+    /// Returns `None` if `scale` is greater than [`MAX_SCALE`](Percentage::MAX_SCALE).
+    pub const fn from_decimal(mut mantissa: u64, mut scale: u32) -> Option<Self> {
+        if scale > Self::MAX_SCALE {
+            return None;
+        }
+        while scale != 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Some(Self {
+            mantissa,
+            scale: scale as u8,
+        })
+    }
+
+    /// Computes the percentage of the total rounding down.
+    ///
+    /// Returns `None` on overflow.
+    pub fn of(self, total: Size) -> Option<Size> {
+        // cannot overflow: u64::MAX^2 < u128::MAX
+        let size = (total.0 as u128) * (self.mantissa as u128) / (100 * self.denominator());
+        u64::try_from(size).ok().map(Size)
+    }
+
+    const fn denominator(self) -> u128 {
+        10_u128.pow(self.scale as u32)
+    }
+}
+
+impl PartialOrd for Percentage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Percentage {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // cannot overflow: u64::MAX * 10^19 < u128::MAX
+        let a = (self.mantissa as u128) * other.denominator();
+        let b = (other.mantissa as u128) * self.denominator();
+        a.cmp(&b)
+    }
+}
+
+impl Display for Percentage {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let denominator = 10_u64.pow(self.scale as u32);
+        write!(f, "{}", self.mantissa / denominator)?;
+        if self.scale != 0 {
+            write!(
+                f,
+                ".{:0width$}",
+                self.mantissa % denominator,
+                width = self.scale as usize
+            )?;
+        }
+        f.write_str("%")
+    }
+}
+
+impl FromStr for Percentage {
+    type Err = SizeError;
+    fn from_str(other: &str) -> Result<Self, Self::Err> {
+        let number = other.trim().strip_suffix('%').ok_or(SizeError)?.trim_end();
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(..) => return Err(SizeError),
+            None => (number, ""),
+        };
+        if integer.is_empty()
+            || !integer.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(SizeError);
+        }
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > Self::MAX_SCALE as usize {
+            return Err(SizeError);
+        }
+        let scale = fraction.len() as u32;
+        let integer: u64 = integer.parse().map_err(|_| SizeError)?;
+        let fraction: u64 = if fraction.is_empty() {
+            0
+        } else {
+            fraction.parse().map_err(|_| SizeError)?
+        };
+        let mantissa = integer
+            .checked_mul(10_u64.pow(scale))
+            .and_then(|m| m.checked_add(fraction))
+            .ok_or(SizeError)?;
+        Self::from_decimal(mantissa, scale).ok_or(SizeError)
+    }
+}
+
+#[cfg(any(feature = "arbitrary", all(test, not(feature = "no_std"))))]
+impl<'a> arbitrary::Arbitrary<'a> for Percentage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::from_decimal(u.arbitrary()?, u.int_in_range(0..=Self::MAX_SCALE)?)
+            .ok_or(arbitrary::Error::IncorrectFormat)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RelativeSize {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::serialize_str::<{ RelativeSize::MAX_STRING_LEN }, _, _>(self, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Deserialize<'a> for RelativeSize {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        crate::deserialize_str(d, "a size or a percentage")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for RelativeSize {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "RelativeSize".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::RelativeSize".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        crate::string_schema(
+            "Size with optional unit k, m, g or t (powers of 1024) or the percentage of the total",
+            r"^\s*(\+?[0-9]+\s*[kKmMgGtT]?|[0-9]+(\.[0-9]+)?\s*%)\s*$",
+            &["512m", "25%", "12.5%"],
+        )
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!("1k", RelativeSize::Absolute(Size(1024)).to_string());
+        assert_eq!("25%", percent(25, 0).to_string());
+        assert_eq!("12.5%", percent(125, 1).to_string());
+        assert_eq!("0.05%", percent(5, 2).to_string());
+        assert_eq!("0%", percent(0, 0).to_string());
+        assert_eq!("0%", percent(0, 19).to_string());
+        assert_eq!("1%", percent(100, 2).to_string());
+        assert_eq!(
+            Percentage::MAX_STRING_LEN,
+            percent(u64::MAX, 19).to_string().len()
+        );
+        assert_eq!(Percentage::MAX_STRING_LEN, percent(1, 19).to_string().len());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            RelativeSize::Absolute(Size(512 << 20)),
+            "512m".parse().unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(25, 0)),
+            "25%".parse().unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(125, 1)),
+            " 12.50 %\n".parse().unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(0, 0)),
+            "0.0%".parse().unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(u64::MAX, 0)),
+            "18446744073709551615%".parse().unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(1, 19)),
+            "0.0000000000000000001%".parse().unwrap()
+        );
+        for s in [
+            "",
+            "%",
+            "1%%",
+            "-1%",
+            "+1%",
+            ".5%",
+            "5.%",
+            "1.2.3%",
+            "1,5%",
+            "1e2%",
+            "0.00000000000000000001%",
+            "18446744073709551616%",
+            "1844674407370955161.6%",
+        ] {
+            assert_eq!(
+                "Err(SizeError)",
+                format!("{:?}", s.parse::<RelativeSize>()),
+                "s = `{}`",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let total = Size(16 << 30);
+        assert_eq!(
+            Some(Size(512)),
+            RelativeSize::Absolute(Size(512)).resolve(total)
+        );
+        assert_eq!(Some(Size(4 << 30)), resolve("25%", total));
+        assert_eq!(Some(Size(2 << 30)), resolve("12.5%", total));
+        assert_eq!(Some(Size(32 << 30)), resolve("200%", total));
+        assert_eq!(Some(Size(0)), resolve("0%", total));
+        assert_eq!(Some(Size(3)), resolve("33.3333%", Size(10)));
+        assert_eq!(Some(Size(u64::MAX)), resolve("100%", Size(u64::MAX)));
+        assert_eq!(None, resolve("100.1%", Size(u64::MAX)));
+        assert_eq!(None, resolve("18446744073709551615%", Size(u64::MAX)));
+        assert_eq!(
+            Some(Size(u64::MAX / 100)),
+            resolve("18446744073709551615%", Size(1))
+        );
+    }
+
+    #[test]
+    fn test_ord() {
+        assert!(percent(125, 1) < percent(13, 0));
+        assert!(percent(u64::MAX, 19) < percent(2, 0));
+        assert!(percent(1, 19) > percent(0, 0));
+        assert_eq!(Ordering::Equal, percent(5, 1).cmp(&percent(50, 2)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_io() {
+        assert_eq!(
+            "\"12.5%\"",
+            serde_json::to_string(&RelativeSize::Relative(percent(125, 1))).unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Relative(percent(25, 0)),
+            serde_yaml::from_str("25%").unwrap()
+        );
+        assert_eq!(
+            RelativeSize::Absolute(Size(1024)),
+            serde_json::from_str("\"1k\"").unwrap()
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema() {
+        crate::str_schema::tests::check_schema::<RelativeSize>(
+            &[
+                "0", "1", "9", "10", "k", "m", "g", "t", "%", " ", "\t", "+", "-", ".",
+            ],
+            7,
+            |_| false,
+        );
+    }
+
+    #[test]
+    fn display_parse_symmetry() {
+        arbtest(|u| {
+            let expected: RelativeSize = u.arbitrary()?;
+            let string = expected.to_string();
+            let actual: RelativeSize = string.parse().unwrap();
+            assert_eq!(expected, actual, "string = `{}`", string);
+            Ok(())
+        });
+    }
+
+    fn percent(mantissa: u64, scale: u32) -> Percentage {
+        Percentage::from_decimal(mantissa, scale).unwrap()
+    }
+
+    fn resolve(s: &str, total: Size) -> Option<Size> {
+        s.parse::<RelativeSize>().unwrap().resolve(total)
+    }
+}
@@ -0,0 +1,46 @@
+use schemars::json_schema;
+
+use crate::Size;
+
+impl schemars::JsonSchema for Size {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "Size".into()
+    }
+
+    fn schema_id() -> alloc::borrow::Cow<'static, str> {
+        "human_units::Size".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        json_schema!({
+            "description": "Size with optional unit k, m, g or t (powers of 1024) or the number of bytes",
+            "type": ["string", "integer"],
+            "pattern": PATTERN,
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "examples": ["512m", "1g", 4096],
+        })
+    }
+}
+
+/// Matches exactly the strings that `Size::from_str` accepts.
+const PATTERN: &str = r"^\s*\+?[0-9]+\s*[kKmMgGtT]?\s*$";
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+
+    use super::*;
+    use crate::str_schema::tests::check_schema;
+
+    #[test]
+    fn test_schema() {
+        check_schema::<Size>(
+            &[
+                "0", "1", "9", "10", "k", "K", "m", "M", "g", "G", "t", "T", "p", "b", " ", "\t",
+                "\n", "+", "-", ".",
+            ],
+            7,
+            |_| false,
+        );
+    }
+}
@@ -1,14 +1,22 @@
 use core::fmt::Formatter;
 use core::fmt::Write;
 
+use serde::de::Unexpected;
+
 use crate::Buffer;
+use crate::CheckedVisitor;
+use crate::ExpectedUnits;
 use crate::Size;
+use crate::SIZE_UNITS;
 
 impl serde::Serialize for Size {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        if !s.is_human_readable() {
+            return s.serialize_u64(self.0);
+        }
         let mut buf = Buffer::<{ Size::MAX_STRING_LEN }>::new();
         let _ = write!(&mut buf, "{}", self);
         s.serialize_str(unsafe { core::str::from_utf8_unchecked(buf.as_slice()) })
@@ -20,7 +28,31 @@ impl<'a> serde::Deserialize<'a> for Size {
     where
         D: serde::Deserializer<'a>,
     {
-        d.deserialize_str(SizeVisitor)
+        if d.is_human_readable() {
+            d.deserialize_any(SizeVisitor)
+        } else {
+            d.deserialize_u64(SizeVisitor)
+        }
+    }
+}
+
+/// Deserializes the size the same way as [`Size`] does and checks it with `check`.
+pub(crate) fn deserialize_size_checked<'a, D, T, E>(
+    d: D,
+    check: impl FnOnce(Size) -> Result<T, E>,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'a>,
+    E: core::fmt::Display,
+{
+    let visitor = CheckedVisitor {
+        inner: SizeVisitor,
+        check,
+    };
+    if d.is_human_readable() {
+        d.deserialize_any(visitor)
+    } else {
+        d.deserialize_u64(visitor)
     }
 }
 
@@ -30,14 +62,42 @@ impl<'a> serde::de::Visitor<'a> for SizeVisitor {
     type Value = Size;
 
     fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
-        write!(formatter, "a string obtained by `Size::to_string`")
+        write!(
+            formatter,
+            "a string obtained by `Size::to_string` or the number of bytes"
+        )
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        value.parse().map_err(|_| E::custom("invalid size"))
+        Size::parse(value).map_err(|reason| {
+            E::invalid_value(
+                Unexpected::Str(value),
+                &ExpectedUnits {
+                    what: "a size",
+                    units: SIZE_UNITS,
+                    reason,
+                },
+            )
+        })
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Size(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        u64::try_from(value)
+            .map(Size)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
     }
 }
 
@@ -53,6 +113,55 @@ mod tests {
         assert_eq!("\"1k\"", serde_json::to_string(&Size(1024)).unwrap());
         assert_eq!(Size(1024), serde_json::from_str("\"1k\"").unwrap());
         assert_eq!(Size(1024), serde_yaml::from_str("1024").unwrap());
+        assert_eq!(Size(1048576), serde_json::from_str("1048576").unwrap());
+        assert_eq!(
+            Size(u64::MAX),
+            serde_json::from_str("18446744073709551615").unwrap()
+        );
+        assert_eq!(
+            SizeWrapper { size: Size(1024) },
+            toml::from_str("size = 1024").unwrap()
+        );
+        for s in ["-1", "1.5", "null", "true", "[]", "{}"] {
+            assert!(serde_json::from_str::<Size>(s).is_err(), "s = `{}`", s);
+        }
+    }
+
+    #[test]
+    fn test_serde_errors() {
+        for (s, error) in [
+            (
+                "\"10x\"",
+                "invalid value: string \"10x\", expected a size with optional unit k, m, g or t (unknown unit) at line 1 column 5",
+            ),
+            (
+                "\"1kb\"",
+                "invalid value: string \"1kb\", expected a size with optional unit k, m, g or t (unknown unit) at line 1 column 5",
+            ),
+            (
+                "\"1.5k\"",
+                "invalid value: string \"1.5k\", expected a size with optional unit k, m, g or t (not a non-negative integer) at line 1 column 6",
+            ),
+            (
+                "\"\"",
+                "invalid value: string \"\", expected a size with optional unit k, m, g or t (no number) at line 1 column 2",
+            ),
+            (
+                "\"16777216t\"",
+                "invalid value: string \"16777216t\", expected a size with optional unit k, m, g or t (the value is too large) at line 1 column 11",
+            ),
+            (
+                "-1",
+                "invalid value: integer `-1`, expected a string obtained by `Size::to_string` or the number of bytes at line 1 column 2",
+            ),
+        ] {
+            assert_eq!(
+                error,
+                serde_json::from_str::<Size>(s).unwrap_err().to_string(),
+                "s = `{}`",
+                s
+            );
+        }
     }
 
     #[test]
@@ -94,6 +203,34 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_serde_bincode() {
+        assert_eq!(8, bincode::serialize(&Size(u64::MAX)).unwrap().len());
+        arbtest(|u| {
+            let expected: SizeWrapper = u.arbitrary()?;
+            let bytes = bincode::serialize(&expected).unwrap();
+            assert_eq!(bincode::serialize(&expected.size.0).unwrap(), bytes);
+            let actual = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_serde_postcard() {
+        assert_eq!(
+            vec![0x80, 0x08],
+            postcard::to_allocvec(&Size(1024)).unwrap()
+        );
+        arbtest(|u| {
+            let expected: SizeWrapper = u.arbitrary()?;
+            let bytes = postcard::to_allocvec(&expected).unwrap();
+            let actual = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(expected, actual);
+            Ok(())
+        });
+    }
+
     #[derive(
         serde::Serialize, serde::Deserialize, arbitrary::Arbitrary, Debug, PartialEq, Eq, Clone,
     )]
@@ -0,0 +1,247 @@
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use core::fmt::Display;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use bytes::BufMut;
+use bytes::BytesMut;
+use postgres_types::to_sql_checked;
+use postgres_types::FromSql;
+use postgres_types::IsNull;
+use postgres_types::ToSql;
+use postgres_types::Type;
+
+use crate::Duration;
+use crate::DurationConversionError;
+use crate::Size;
+use crate::SqlText;
+
+type BoxError = Box<dyn std::error::Error + Sync + Send>;
+
+impl ToSql for Size {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        i64::try_from(self.0)?.to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Size {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(Self(u64::try_from(i64::from_sql(ty, raw)?)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <i64 as FromSql>::accepts(ty)
+    }
+}
+
+/// Encodes the duration as `INTERVAL` that consists of microseconds only.
+impl ToSql for Duration {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let nanos = self.0.as_nanos();
+        if nanos % NANOS_PER_MICRO != 0 {
+            return Err(Box::new(DurationConversionError::Inexact));
+        }
+        let micros = i64::try_from(nanos / NANOS_PER_MICRO)
+            .map_err(|_| DurationConversionError::OutOfRange)?;
+        out.put_i64(micros);
+        out.put_i32(0);
+        out.put_i32(0);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+
+    to_sql_checked!();
+}
+
+/// Decodes `INTERVAL` assuming 24-hour days and 30-day months like `EXTRACT(EPOCH FROM ...)` does.
+impl<'a> FromSql<'a> for Duration {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        if raw.len() != INTERVAL_LEN {
+            return Err("invalid interval length".into());
+        }
+        let micros = i64::from_be_bytes(raw[0..8].try_into()?);
+        let days = i32::from_be_bytes(raw[8..12].try_into()?);
+        let months = i32::from_be_bytes(raw[12..16].try_into()?);
+        let days = days as i128 + months as i128 * DAYS_PER_MONTH;
+        let micros = micros as i128 + days * MICROS_PER_DAY;
+        let micros = u128::try_from(micros).map_err(|_| DurationConversionError::Negative)?;
+        let nanos = micros * NANOS_PER_MICRO;
+        Ok(Self(StdDuration::new(
+            (nanos / NANOS_PER_SEC) as u64,
+            (nanos % NANOS_PER_SEC) as u32,
+        )))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+}
+
+impl<T: Display + core::fmt::Debug> ToSql for SqlText<T> {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        self.0.to_string().as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a, T> FromSql<'a> for SqlText<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        // the error is boxed via `Display` because parsing errors implement `Error` only with `std`
+        let s = <&str as FromSql>::from_sql(ty, raw)?;
+        s.parse()
+            .map(Self)
+            .map_err(|e: T::Err| e.to_string().into())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+const INTERVAL_LEN: usize = 16;
+const DAYS_PER_MONTH: i128 = 30;
+const MICROS_PER_DAY: i128 = 24 * 60 * 60 * 1_000_000;
+const NANOS_PER_MICRO: u128 = 1_000;
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use arbtest::arbtest;
+
+    use super::*;
+
+    #[test]
+    fn test_int8_fixtures() {
+        let raw = include_bytes!("../fixtures/postgres/int8-1073741824");
+        assert_eq!(Size(1 << 30), Size::from_sql(&Type::INT8, raw).unwrap());
+        assert_eq!(raw.as_slice(), to_bytes(&Size(1 << 30), &Type::INT8));
+        let raw = include_bytes!("../fixtures/postgres/int8-minus-1");
+        assert!(Size::from_sql(&Type::INT8, raw).is_err());
+        assert!(Size(u64::MAX)
+            .to_sql_checked(&Type::INT8, &mut BytesMut::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_interval_fixtures() {
+        for (raw, expected) in [
+            (
+                include_bytes!("../fixtures/postgres/interval-0").as_slice(),
+                StdDuration::ZERO,
+            ),
+            (
+                include_bytes!("../fixtures/postgres/interval-1-microsecond"),
+                StdDuration::from_micros(1),
+            ),
+            (
+                include_bytes!("../fixtures/postgres/interval-1-day-02-03-04.5"),
+                StdDuration::from_millis(((24 + 2) * 3600 + 3 * 60 + 4) * 1000 + 500),
+            ),
+            (
+                include_bytes!("../fixtures/postgres/interval-1-mon"),
+                StdDuration::from_secs(30 * 24 * 3600),
+            ),
+            (
+                include_bytes!("../fixtures/postgres/interval-1-year-2-mons-minus-3-days"),
+                StdDuration::from_secs((14 * 30 - 3) * 24 * 3600),
+            ),
+        ] {
+            assert_eq!(
+                Duration(expected),
+                Duration::from_sql(&Type::INTERVAL, raw).unwrap()
+            );
+        }
+        let raw = include_bytes!("../fixtures/postgres/interval-minus-1-sec");
+        assert_eq!(
+            "duration is negative",
+            Duration::from_sql(&Type::INTERVAL, raw)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(Duration::from_sql(&Type::INTERVAL, &[0; 8]).is_err());
+    }
+
+    #[test]
+    fn test_interval_encoding() {
+        assert_eq!(
+            include_bytes!("../fixtures/postgres/interval-0").as_slice(),
+            to_bytes(&Duration(StdDuration::ZERO), &Type::INTERVAL)
+        );
+        assert_eq!(
+            include_bytes!("../fixtures/postgres/interval-1-microsecond").as_slice(),
+            to_bytes(&Duration(StdDuration::from_micros(1)), &Type::INTERVAL)
+        );
+        for (duration, error) in [
+            (StdDuration::from_nanos(1), "duration is too precise"),
+            (StdDuration::from_secs(u64::MAX), "duration is out of range"),
+        ] {
+            assert_eq!(
+                error,
+                Duration(duration)
+                    .to_sql_checked(&Type::INTERVAL, &mut BytesMut::new())
+                    .err()
+                    .unwrap()
+                    .to_string()
+            );
+        }
+        assert!(Duration(StdDuration::ZERO)
+            .to_sql_checked(&Type::INT8, &mut BytesMut::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        let raw = to_bytes(&SqlText(Size(512 << 20)), &Type::TEXT);
+        assert_eq!(b"512m".as_slice(), raw);
+        assert_eq!(
+            SqlText(Size(512 << 20)),
+            SqlText::from_sql(&Type::TEXT, &raw).unwrap()
+        );
+        assert!(SqlText::<Duration>::from_sql(&Type::TEXT, b"1x").is_err());
+        assert!(!<SqlText<Size> as ToSql>::accepts(&Type::INT8));
+    }
+
+    #[test]
+    fn test_symmetry() {
+        arbtest(|u| {
+            let expected = Size(u.int_in_range(0..=i64::MAX as u64)?);
+            let raw = to_bytes(&expected, &Type::INT8);
+            assert_eq!(expected, Size::from_sql(&Type::INT8, &raw).unwrap());
+            let expected = Duration(StdDuration::from_micros(
+                u.int_in_range(0..=i64::MAX as u64)?,
+            ));
+            let raw = to_bytes(&expected, &Type::INTERVAL);
+            assert_eq!(expected, Duration::from_sql(&Type::INTERVAL, &raw).unwrap());
+            let expected = SqlText(Duration(u.arbitrary()?));
+            let raw = to_bytes(&expected, &Type::VARCHAR);
+            assert_eq!(expected, SqlText::from_sql(&Type::VARCHAR, &raw).unwrap());
+            Ok(())
+        });
+    }
+
+    fn to_bytes<T: ToSql>(value: &T, ty: &Type) -> Vec<u8> {
+        let mut out = BytesMut::new();
+        value.to_sql_checked(ty, &mut out).unwrap();
+        out.to_vec()
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use core::fmt::Display;
+use core::str::FromStr;
+use core::time::Duration as StdDuration;
+
+use rusqlite::types::FromSql;
+use rusqlite::types::FromSqlError;
+use rusqlite::types::FromSqlResult;
+use rusqlite::types::ToSql;
+use rusqlite::types::ToSqlOutput;
+use rusqlite::types::ValueRef;
+
+use crate::Duration;
+use crate::Size;
+use crate::SqlText;
+
+impl ToSql for Size {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let bytes = i64::try_from(self.0)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Ok(bytes.into())
+    }
+}
+
+impl FromSql for Size {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let bytes = value.as_i64()?;
+        u64::try_from(bytes)
+            .map(Self)
+            .map_err(|_| FromSqlError::OutOfRange(bytes))
+    }
+}
+
+impl ToSql for Duration {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let nanos = i64::try_from(self.0.as_nanos())
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Ok(nanos.into())
+    }
+}
+
+impl FromSql for Duration {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let nanos = value.as_i64()?;
+        u64::try_from(nanos)
+            .map(|nanos| Self(StdDuration::from_nanos(nanos)))
+            .map_err(|_| FromSqlError::OutOfRange(nanos))
+    }
+}
+
+impl<T: Display> ToSql for SqlText<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(self.0.to_string().into())
+    }
+}
+
+impl<T> FromSql for SqlText<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        // the error is boxed via `Display` because parsing errors implement `Error` only with `std`
+        value
+            .as_str()?
+            .parse()
+            .map(Self)
+            .map_err(|e: T::Err| FromSqlError::Other(e.to_string().into()))
+    }
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use arbtest::arbtest;
+    use rusqlite::Connection;
+
+    use super::*;
+
+    #[test]
+    fn test_integer() {
+        let conn = connect();
+        let size = Size(512 << 20);
+        let duration = Duration(StdDuration::new(30, 1));
+        conn.execute(
+            "INSERT INTO t (size, duration) VALUES (?1, ?2)",
+            (size, duration),
+        )
+        .unwrap();
+        let (actual_size, actual_duration, size_type, duration_type): (
+            Size,
+            Duration,
+            String,
+            String,
+        ) = conn
+            .query_row(
+                "SELECT size, duration, typeof(size), typeof(duration) FROM t",
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(size, actual_size);
+        assert_eq!(duration, actual_duration);
+        assert_eq!("integer", size_type);
+        assert_eq!("integer", duration_type);
+        let raw: (i64, i64) = conn
+            .query_row("SELECT size, duration FROM t", (), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((512 << 20, 30_000_000_001), raw);
+    }
+
+    #[test]
+    fn test_text() {
+        let conn = connect();
+        let size = SqlText(Size(512 << 20));
+        let duration = SqlText(Duration(StdDuration::from_secs(30)));
+        conn.execute(
+            "INSERT INTO t (size, duration) VALUES (?1, ?2)",
+            (size, duration),
+        )
+        .unwrap();
+        let raw: (String, String) = conn
+            .query_row("SELECT size, duration FROM t", (), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(("512m".into(), "30s".into()), raw);
+        let actual: (SqlText<Size>, SqlText<Duration>) = conn
+            .query_row("SELECT size, duration FROM t", (), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((size, duration), actual);
+    }
+
+    #[test]
+    fn test_errors() {
+        let conn = connect();
+        assert!(conn
+            .execute("INSERT INTO t (size) VALUES (?1)", [Size(u64::MAX)])
+            .is_err());
+        assert!(conn
+            .execute(
+                "INSERT INTO t (duration) VALUES (?1)",
+                [Duration(StdDuration::MAX)]
+            )
+            .is_err());
+        conn.execute("INSERT INTO t (size, duration) VALUES (-1, '1x')", ())
+            .unwrap();
+        assert!(conn
+            .query_row("SELECT size FROM t", (), |row| row.get::<_, Size>(0))
+            .is_err());
+        assert!(conn
+            .query_row("SELECT size FROM t", (), |row| row.get::<_, Duration>(0))
+            .is_err());
+        assert!(conn
+            .query_row("SELECT duration FROM t", (), |row| {
+                row.get::<_, SqlText<Duration>>(0)
+            })
+            .is_err());
+        assert!(conn
+            .query_row("SELECT duration FROM t", (), |row| row
+                .get::<_, Duration>(0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_symmetry() {
+        let conn = connect();
+        let mut select = conn.prepare("SELECT ?1, ?2, ?3, ?4").unwrap();
+        arbtest(|u| {
+            let size = Size(u.int_in_range(0..=i64::MAX as u64)?);
+            let duration = Duration(StdDuration::from_nanos(
+                u.int_in_range(0..=i64::MAX as u64)?,
+            ));
+            let text_size = SqlText(Size(u.arbitrary()?));
+            let text_duration = SqlText(Duration(u.arbitrary()?));
+            let actual: (Size, Duration, SqlText<Size>, SqlText<Duration>) = select
+                .query_row((size, duration, text_size, text_duration), |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })
+                .unwrap();
+            assert_eq!((size, duration, text_size, text_duration), actual);
+            Ok(())
+        });
+    }
+
+    fn connect() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE t (size, duration)", ()).unwrap();
+        conn
+    }
+}
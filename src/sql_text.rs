@@ -0,0 +1,40 @@
+use core::ops::Deref;
+use core::ops::DerefMut;
+
+/**
+Database column that stores the value as text.
+
+By default [`Size`](crate::Size) is stored as the number of bytes,
+[`Duration`](crate::Duration) is stored as the number of nanoseconds in SQLite
+and as `INTERVAL` in PostgreSQL.
+This wrapper stores the value in `TEXT` column in its canonical string form (`512m`, `30s`)
+and parses it when the column is read.
+The wrapper works with any type that implements `Display` and `FromStr`.
+*/
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    any(feature = "arbitrary", all(test, not(feature = "no_std"))),
+    derive(arbitrary::Arbitrary)
+)]
+#[repr(transparent)]
+pub struct SqlText<T>(pub T);
+
+impl<T> From<T> for SqlText<T> {
+    fn from(other: T) -> Self {
+        Self(other)
+    }
+}
+
+impl<T> Deref for SqlText<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SqlText<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
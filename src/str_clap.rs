@@ -0,0 +1,30 @@
+use core::fmt::Display;
+use std::ffi::OsStr;
+
+use clap::error::ErrorKind;
+
+/// Converts command line argument to UTF-8 string.
+pub(crate) fn clap_str<'a>(cmd: &clap::Command, value: &'a OsStr) -> Result<&'a str, clap::Error> {
+    value
+        .to_str()
+        .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))
+}
+
+/// Creates an error that quotes the argument, its value and what was expected instead.
+pub(crate) fn clap_invalid_value(
+    cmd: &clap::Command,
+    arg: Option<&clap::Arg>,
+    value: &str,
+    expected: impl Display,
+) -> clap::Error {
+    let message = match arg {
+        Some(arg) => alloc::format!(
+            "invalid value '{}' for '{}': expected {}\n",
+            value,
+            arg,
+            expected
+        ),
+        None => alloc::format!("invalid value '{}': expected {}\n", value, expected),
+    };
+    clap::Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+}
@@ -0,0 +1,78 @@
+use alloc::string::String;
+use core::fmt::Display;
+
+/// Unit spellings accepted by `Size::from_str` and the max. number for each of them.
+pub(crate) const SIZE_SPELLINGS: [(&str, u128); 9] = [
+    ("", u64::MAX as u128),
+    ("k", (u64::MAX >> 10) as u128),
+    ("K", (u64::MAX >> 10) as u128),
+    ("m", (u64::MAX >> 20) as u128),
+    ("M", (u64::MAX >> 20) as u128),
+    ("g", (u64::MAX >> 30) as u128),
+    ("G", (u64::MAX >> 30) as u128),
+    ("t", (u64::MAX >> 40) as u128),
+    ("T", (u64::MAX >> 40) as u128),
+];
+
+/// Unit spellings accepted by `Duration::from_str` and the max. number for each of them.
+pub(crate) const DURATION_SPELLINGS: [(&str, u128); 8] = [
+    ("", MAX_NANOSECONDS / 1_000_000_000),
+    ("ns", MAX_NANOSECONDS),
+    ("μs", MAX_NANOSECONDS / 1_000),
+    ("ms", MAX_NANOSECONDS / 1_000_000),
+    ("s", MAX_NANOSECONDS / 1_000_000_000),
+    ("m", MAX_NANOSECONDS / (60 * 1_000_000_000)),
+    ("h", MAX_NANOSECONDS / (60 * 60 * 1_000_000_000)),
+    ("d", MAX_NANOSECONDS / (24 * 60 * 60 * 1_000_000_000)),
+];
+
+/// Whitespace before the number, between the number and the unit and after the unit.
+pub(crate) const WHITESPACE: [&str; 6] = ["", " ", "  ", "\t", "\n", "\r\n"];
+
+/// Tokens that turn valid sizes into nearly valid ones.
+pub(crate) const SIZE_TOKENS: [&str; 16] = [
+    "0", "9", "+", "-", ".", ".5", " ", "k", "kk", "b", "B", "KiB", "p", "x", "_", ",",
+];
+
+/// Tokens that turn valid durations into nearly valid ones.
+pub(crate) const DURATION_TOKENS: [&str; 16] = [
+    "0", "9", "+", "-", ".", ".5", " ", "s", "us", "µs", "M", "min", "sec", "x", "_", ",",
+];
+
+/// The greatest number of nanoseconds that `Duration` can hold.
+const MAX_NANOSECONDS: u128 = u64::MAX as u128 * 1_000_000_000 + 999_999_999;
+
+/// Valid string that consists of optional sign, number, unit and whitespace around them.
+pub(crate) struct ValidStr {
+    pub(crate) sign: bool,
+    pub(crate) prefix: &'static str,
+    pub(crate) number: u128,
+    pub(crate) infix: &'static str,
+    pub(crate) unit: &'static str,
+    pub(crate) suffix: &'static str,
+}
+
+impl Display for ValidStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let sign = if self.sign { "+" } else { "" };
+        write!(
+            f,
+            "{}{}{}{}{}{}",
+            self.prefix, sign, self.number, self.infix, self.unit, self.suffix
+        )
+    }
+}
+
+/// Removes the character at `index` (modulo the string length) if `token` is `None`,
+/// otherwise inserts the `token` before this character.
+pub(crate) fn mutate(s: &mut String, index: usize, token: Option<&str>) {
+    let index = index % (s.chars().count() + 1);
+    let i = s.char_indices().nth(index).map_or(s.len(), |(i, _)| i);
+    match token {
+        Some(token) => s.insert_str(i, token),
+        None if i != s.len() => {
+            s.remove(i);
+        }
+        None => {}
+    }
+}
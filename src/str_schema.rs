@@ -0,0 +1,117 @@
+use schemars::json_schema;
+use schemars::Schema;
+
+/// Creates the schema of a string that is parsed by `FromStr` implementation.
+///
+/// The pattern must match exactly the strings that the parser accepts;
+/// the examples must be accepted by the parser.
+pub(crate) fn string_schema(description: &str, pattern: &str, examples: &[&str]) -> Schema {
+    json_schema!({
+        "description": description,
+        "type": "string",
+        "pattern": pattern,
+        "examples": examples,
+    })
+}
+
+/// Creates the schema of the approximate value that consists of integral part,
+/// fractional part and unit.
+///
+/// The value is either a string or `{integer, fraction, unit}` object.
+pub(crate) fn formatted_schema(
+    description: &str,
+    pattern: &str,
+    max_integer: u64,
+    units: &[&str],
+    examples: &[&str],
+) -> Schema {
+    json_schema!({
+        "description": description,
+        "anyOf": [
+            {
+                "type": "string",
+                "pattern": pattern,
+            },
+            {
+                "type": "object",
+                "properties": {
+                    "integer": {"type": "integer", "minimum": 0, "maximum": max_integer},
+                    "fraction": {"type": "integer", "minimum": 0, "maximum": 9},
+                    "unit": {"enum": units},
+                },
+                "required": ["integer", "fraction", "unit"],
+                "additionalProperties": false,
+            },
+        ],
+        "examples": examples,
+    })
+}
+
+#[cfg(all(test, not(feature = "no_std")))]
+pub(crate) mod tests {
+    #![allow(clippy::unwrap_used)]
+    use core::str::FromStr;
+
+    use arbtest::arbtest;
+    use schemars::JsonSchema;
+
+    /// Checks that the schema accepts exactly the strings that `T` parses.
+    ///
+    /// The strings are made by inserting random `tokens` into the examples
+    /// and by removing characters from them.
+    /// Strings with more than `max_digits` consecutive (hexadecimal) digits
+    /// as well as the strings for which `skip` returns `true`
+    /// are only checked to be accepted by the schema when they are parsed successfully:
+    /// the schema describes the syntax, not the range of the values.
+    pub(crate) fn check_schema<T: JsonSchema + FromStr>(
+        tokens: &[&str],
+        max_digits: usize,
+        skip: fn(&str) -> bool,
+    ) {
+        let schema = schemars::schema_for!(T);
+        let value = serde_json::to_value(&schema).unwrap();
+        let validator = jsonschema::validator_for(&value).unwrap();
+        let examples = value["examples"].as_array().unwrap();
+        assert!(!examples.is_empty());
+        for example in examples {
+            assert!(validator.is_valid(example), "example = {}", example);
+            if let Some(s) = example.as_str() {
+                assert!(s.parse::<T>().is_ok(), "example = {}", s);
+            }
+        }
+        let seeds: Vec<&str> = examples.iter().filter_map(|x| x.as_str()).collect();
+        arbtest(|u| {
+            // mutate one of the examples or build the string from scratch
+            let mut s = match u.arbitrary()? {
+                true => u.choose(&seeds)?.to_string(),
+                false => String::new(),
+            };
+            for _ in 0..u.int_in_range(1..=6)? {
+                let i = u.int_in_range(0..=s.len())?;
+                if !s.is_char_boundary(i) {
+                    continue;
+                }
+                match u.int_in_range(0..=2)? {
+                    0 if i != s.len() => {
+                        s.remove(i);
+                    }
+                    _ => s.insert_str(i, u.choose(tokens)?),
+                }
+            }
+            let parsed = s.parse::<T>().is_ok();
+            let valid = validator.is_valid(&serde_json::Value::String(s.clone()));
+            if parsed || !(skip(&s) || max_digit_run(&s) > max_digits) {
+                assert_eq!(parsed, valid, "s = `{}`", s);
+            }
+            Ok(())
+        })
+        .budget_ms(300);
+    }
+
+    fn max_digit_run(s: &str) -> usize {
+        s.split(|ch: char| !ch.is_ascii_hexdigit())
+            .map(str::len)
+            .max()
+            .unwrap_or(0)
+    }
+}
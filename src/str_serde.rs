@@ -0,0 +1,270 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Write;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use serde::de::Unexpected;
+
+use crate::Buffer;
+use crate::ExpectedUnits;
+
+/// Serializes the value as a string using its `Display` implementation.
+///
+/// `N` is the max. length of the value in string form.
+pub(crate) fn serialize_str<const N: usize, T, S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: serde::Serializer,
+{
+    let mut buf = Buffer::<N>::new();
+    let _ = write!(&mut buf, "{}", value);
+    s.serialize_str(unsafe { buf.as_str() })
+}
+
+/// Deserializes the value from a string using its `FromStr` implementation.
+pub(crate) fn deserialize_str<'a, T, D>(d: D, expecting: &'static str) -> Result<T, D::Error>
+where
+    T: FromStr,
+    D: serde::Deserializer<'a>,
+{
+    d.deserialize_str(StrVisitor {
+        expecting,
+        phantom: PhantomData,
+    })
+}
+
+struct StrVisitor<T> {
+    expecting: &'static str,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> serde::de::Visitor<'a> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+/// Visitor that checks the value produced by the inner visitor.
+///
+/// The check is done inside the visitor so that the error contains the position of the value.
+pub(crate) struct CheckedVisitor<V, F> {
+    pub(crate) inner: V,
+    pub(crate) check: F,
+}
+
+impl<'a, V, F, T, C> serde::de::Visitor<'a> for CheckedVisitor<V, F>
+where
+    V: serde::de::Visitor<'a>,
+    F: FnOnce(V::Value) -> Result<T, C>,
+    C: Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        (self.check)(self.inner.visit_str(value)?).map_err(E::custom)
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        (self.check)(self.inner.visit_u64(value)?).map_err(E::custom)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        (self.check)(self.inner.visit_i64(value)?).map_err(E::custom)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'a>,
+    {
+        use serde::de::Error;
+        (self.check)(self.inner.visit_seq(seq)?).map_err(A::Error::custom)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'a>,
+    {
+        use serde::de::Error;
+        (self.check)(self.inner.visit_map(map)?).map_err(A::Error::custom)
+    }
+}
+
+impl serde::de::Expected for ExpectedUnits {
+    fn fmt(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        Display::fmt(self, formatter)
+    }
+}
+
+/// Approximate value that consists of integral part, fractional part and unit.
+pub(crate) trait FormattedParts: Display + FromStr + Sized {
+    /// Struct name.
+    const NAME: &'static str;
+    /// All supported units.
+    const UNITS: &'static [&'static str];
+
+    /// Returns the value if the parts are within the bounds.
+    fn from_parts(integer: u64, fraction: u8, unit: &'static str) -> Option<Self>;
+
+    /// Returns integral part, fractional part and unit.
+    fn to_parts(&self) -> (u64, u8, &'static str);
+}
+
+/// Serializes the value as a string in human-readable formats and as a struct otherwise.
+pub(crate) fn serialize_formatted<const N: usize, T, S>(value: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    T: FormattedParts,
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeStruct;
+    if s.is_human_readable() {
+        return serialize_str::<N, _, _>(value, s);
+    }
+    let (integer, fraction, unit) = value.to_parts();
+    let mut state = s.serialize_struct(T::NAME, 3)?;
+    state.serialize_field("integer", &integer)?;
+    state.serialize_field("fraction", &fraction)?;
+    state.serialize_field("unit", unit)?;
+    state.end()
+}
+
+/// Deserializes the value from a string or a `{integer, fraction, unit}` map.
+pub(crate) fn deserialize_formatted<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    T: FormattedParts,
+    D: serde::Deserializer<'a>,
+{
+    let visitor = FormattedVisitor {
+        phantom: PhantomData,
+    };
+    if d.is_human_readable() {
+        d.deserialize_any(visitor)
+    } else {
+        d.deserialize_struct(T::NAME, FORMATTED_FIELDS, visitor)
+    }
+}
+
+struct FormattedVisitor<T> {
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: FormattedParts> serde::de::Visitor<'a> for FormattedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        write!(
+            formatter,
+            "a string obtained by `{}::to_string` or `{{integer, fraction, unit}}` map",
+            T::NAME
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'a>,
+    {
+        use serde::de::Error;
+        let integer = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let fraction = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        let unit = seq
+            .next_element_seed(UnitSeed(T::UNITS))?
+            .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+        T::from_parts(integer, fraction, unit)
+            .ok_or_else(|| A::Error::invalid_value(Unexpected::Unsigned(integer), &self))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'a>,
+    {
+        use serde::de::Error;
+        let mut integer: Option<u64> = None;
+        let mut fraction: Option<u8> = None;
+        let mut unit: Option<&'static str> = None;
+        while let Some(field) = map.next_key_seed(UnitSeed(FORMATTED_FIELDS))? {
+            match field {
+                "integer" if integer.is_none() => integer = Some(map.next_value()?),
+                "fraction" if fraction.is_none() => fraction = Some(map.next_value()?),
+                "unit" if unit.is_none() => unit = Some(map.next_value_seed(UnitSeed(T::UNITS))?),
+                _ => return Err(A::Error::duplicate_field(field)),
+            }
+        }
+        let integer = integer.ok_or_else(|| A::Error::missing_field("integer"))?;
+        let fraction = fraction.ok_or_else(|| A::Error::missing_field("fraction"))?;
+        let unit = unit.ok_or_else(|| A::Error::missing_field("unit"))?;
+        T::from_parts(integer, fraction, unit)
+            .ok_or_else(|| A::Error::invalid_value(Unexpected::Unsigned(integer), &self))
+    }
+}
+
+/// Deserializes one of the static strings without allocating memory.
+struct UnitSeed(&'static [&'static str]);
+
+impl<'a> serde::de::DeserializeSeed<'a> for UnitSeed {
+    type Value = &'static str;
+
+    fn deserialize<D>(self, d: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        d.deserialize_str(self)
+    }
+}
+
+impl<'a> serde::de::Visitor<'a> for UnitSeed {
+    type Value = &'static str;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        write!(formatter, "one of {:?}", self.0)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.0
+            .iter()
+            .find(|unit| **unit == value)
+            .copied()
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+const FORMATTED_FIELDS: &[&str] = &["integer", "fraction", "unit"];